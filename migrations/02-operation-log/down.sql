@@ -0,0 +1,2 @@
+DROP TABLE operation_change;
+DROP TABLE operation;
@@ -0,0 +1,18 @@
+CREATE TABLE operation (
+    id INTEGER PRIMARY KEY,
+    created_at DATETIME NOT NULL,
+    description TEXT NOT NULL,
+    undone BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE operation_change (
+    id INTEGER PRIMARY KEY,
+    operation_id INTEGER NOT NULL REFERENCES operation (id) ON DELETE CASCADE,
+    entry_id INTEGER NOT NULL,
+    old_timestamp DATETIME,
+    old_message TEXT,
+    old_long TEXT,
+    new_timestamp DATETIME,
+    new_message TEXT,
+    new_long TEXT
+);
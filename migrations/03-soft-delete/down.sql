@@ -0,0 +1 @@
+ALTER TABLE entry DROP COLUMN deleted_at;
@@ -0,0 +1 @@
+ALTER TABLE entry ADD COLUMN deleted_at DATETIME;
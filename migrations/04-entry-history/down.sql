@@ -0,0 +1,3 @@
+DROP TABLE entry_history;
+ALTER TABLE entry DROP COLUMN updated_at;
+ALTER TABLE entry DROP COLUMN created_at;
@@ -0,0 +1,20 @@
+ALTER TABLE entry ADD COLUMN created_at DATETIME;
+ALTER TABLE entry ADD COLUMN updated_at DATETIME;
+
+CREATE TABLE entry_history (
+    id INTEGER PRIMARY KEY,
+    entry_id INTEGER NOT NULL,
+    changed_at DATETIME NOT NULL,
+    action TEXT NOT NULL,
+    date DATE NOT NULL,
+    time TIME NOT NULL,
+    message TEXT NOT NULL,
+    long TEXT
+);
+
+CREATE INDEX entry_history_entry_id ON entry_history (entry_id);
+
+-- Record the state of all existing entries as the start of their history.
+INSERT INTO entry_history (entry_id, changed_at, action, date, time, message, long)
+SELECT id, datetime('now', 'localtime'), 'import', date, time, message, long
+FROM entry;
@@ -0,0 +1,2 @@
+DROP TABLE seal_entry;
+DROP TABLE seal;
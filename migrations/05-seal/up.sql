@@ -0,0 +1,15 @@
+CREATE TABLE seal (
+    id INTEGER PRIMARY KEY,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    sealed_at DATETIME NOT NULL,
+    previous_hash TEXT NOT NULL,
+    hash TEXT NOT NULL
+);
+
+CREATE TABLE seal_entry (
+    seal_id INTEGER NOT NULL REFERENCES seal (id),
+    entry_id INTEGER NOT NULL,
+    hash TEXT NOT NULL,
+    PRIMARY KEY (seal_id, entry_id)
+);
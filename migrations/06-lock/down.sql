@@ -0,0 +1,2 @@
+ALTER TABLE entry_history DROP COLUMN forced;
+DROP TABLE lock;
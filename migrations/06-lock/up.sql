@@ -0,0 +1,8 @@
+CREATE TABLE lock (
+    id INTEGER PRIMARY KEY,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    locked_at DATETIME NOT NULL
+);
+
+ALTER TABLE entry_history ADD COLUMN forced BOOLEAN NOT NULL DEFAULT FALSE;
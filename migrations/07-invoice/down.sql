@@ -0,0 +1,2 @@
+DROP TABLE invoice_entry;
+DROP TABLE invoice;
@@ -0,0 +1,15 @@
+CREATE TABLE invoice (
+    id INTEGER PRIMARY KEY,
+    number TEXT NOT NULL UNIQUE,
+    client TEXT NOT NULL,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    issued_at DATETIME NOT NULL,
+    total INTEGER NOT NULL,
+    currency TEXT NOT NULL
+);
+
+CREATE TABLE invoice_entry (
+    invoice_id INTEGER NOT NULL REFERENCES invoice (id),
+    entry_id INTEGER PRIMARY KEY
+);
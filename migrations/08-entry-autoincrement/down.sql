@@ -0,0 +1,17 @@
+CREATE TABLE entry_new (
+    id INTEGER PRIMARY KEY,
+    date DATE NOT NULL,
+    time TIME NOT NULL,
+    message TEXT NOT NULL,
+    long TEXT,
+    deleted_at DATETIME,
+    created_at DATETIME,
+    updated_at DATETIME
+);
+
+INSERT INTO entry_new (id, date, time, message, long, deleted_at, created_at, updated_at)
+SELECT id, date, time, message, long, deleted_at, created_at, updated_at
+FROM entry;
+
+DROP TABLE entry;
+ALTER TABLE entry_new RENAME TO entry;
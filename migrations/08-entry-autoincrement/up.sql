@@ -0,0 +1,32 @@
+-- Rebuild the entry table with AUTOINCREMENT, so that the IDs of purged
+-- entries are never given to new ones, which would then be mixed up with
+-- them in the history, seals and invoices.
+CREATE TABLE entry_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date DATE NOT NULL,
+    time TIME NOT NULL,
+    message TEXT NOT NULL,
+    long TEXT,
+    deleted_at DATETIME,
+    created_at DATETIME,
+    updated_at DATETIME
+);
+
+INSERT INTO entry_new (id, date, time, message, long, deleted_at, created_at, updated_at)
+SELECT id, date, time, message, long, deleted_at, created_at, updated_at
+FROM entry;
+
+DROP TABLE entry;
+ALTER TABLE entry_new RENAME TO entry;
+
+-- Continue after all IDs which have been used so far, including the ones
+-- of already purged entries.
+DELETE FROM sqlite_sequence WHERE name = 'entry';
+INSERT INTO sqlite_sequence (name, seq)
+SELECT 'entry', coalesce(max(id), 0) FROM (
+    SELECT id FROM entry
+    UNION ALL SELECT entry_id FROM entry_history
+    UNION ALL SELECT entry_id FROM operation_change
+    UNION ALL SELECT entry_id FROM seal_entry
+    UNION ALL SELECT entry_id FROM invoice_entry
+);
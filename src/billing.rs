@@ -0,0 +1,166 @@
+//! Billable time and the hourly rates it earns.
+
+use crate::config::{Client, Config, Project};
+use crate::model::Entry;
+use crate::rounding::Rounding;
+use anyhow::Result;
+use chrono::Duration;
+
+/// Tag marking a single entry as billable, regardless of the config.
+pub const BILLABLE_TAG: &str = "billable";
+
+/// Tag marking a single entry as not billable, regardless of the config.
+pub const NON_BILLABLE_TAG: &str = "nonbillable";
+
+/// Whether the time described by an entry is billable, at which rate and how
+/// it is rounded.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Billing {
+    pub billable: bool,
+    pub rate: Option<f64>,
+    /// The currency of the rate, which is the one of the matching client,
+    /// falling back to the global one.
+    pub currency: String,
+    pub rounding: Option<Rounding>,
+}
+
+impl Billing {
+    /// Determines the billing of an entry.
+    ///
+    /// The `#billable` and `#nonbillable` tags take precedence over the first
+    /// matching project, which in turn takes precedence over clients, whose
+    /// entries are billable. Entries matching neither are billable if
+    /// `default_billable` is set. The rate is the one of the first matching
+    /// project with a rate, falling back to the one of the first matching
+    /// client. The rounding is the one of the matching client, falling back
+    /// to the global one.
+    pub fn of(entry: &Entry, config: &Config) -> Result<Self> {
+        let project = project_of(entry, config)?;
+        let mut client = None;
+        for c in &config.clients {
+            if entry.matches(&c.filter)? {
+                client = Some(c);
+                break;
+            }
+        }
+
+        let tagged = |tag| entry.tags().any(|t| t.eq_ignore_ascii_case(tag));
+        let billable = if tagged(BILLABLE_TAG) {
+            true
+        } else if tagged(NON_BILLABLE_TAG) {
+            false
+        } else if let Some(project) = project {
+            project.billable
+        } else {
+            client.is_some() || config.default_billable
+        };
+
+        Ok(Self {
+            billable,
+            rate: project.and_then(|p| p.rate).or(client.map(|c| c.rate)),
+            currency: client.map_or(&config.currency, |c| &c.currency).clone(),
+            rounding: config.rounding_for(client),
+        })
+    }
+
+    /// Returns the amount in cents earned in the duration, which is zero for
+    /// time which is not billable.
+    pub fn amount(&self, duration: Duration) -> i64 {
+        match (self.billable, self.rate) {
+            (true, Some(rate)) => amount(duration, rate),
+            _ => 0,
+        }
+    }
+}
+
+/// Returns the hourly rate at which the entry is billed to the given client:
+/// the rate of the first matching project, falling back to the one of the
+/// client, as in [`Billing::of`].
+pub fn rate_for(entry: &Entry, config: &Config, client: &Client) -> Result<f64> {
+    Ok(project_of(entry, config)?
+        .and_then(|p| p.rate)
+        .unwrap_or(client.rate))
+}
+
+/// Returns the first project matching the entry.
+fn project_of<'c>(entry: &Entry, config: &'c Config) -> Result<Option<&'c Project>> {
+    for project in &config.projects {
+        if entry.matches(&project.filter)? {
+            return Ok(Some(project));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the amount in cents for the duration billed at the hourly rate.
+pub fn amount(duration: Duration, rate: f64) -> i64 {
+    (duration.num_seconds() as f64 / 3600.0 * rate * 100.0).round() as i64
+}
+
+/// Formats an amount in cents with its currency, e.g. `1234.50 EUR`.
+pub fn format_amount(cents: i64, currency: &str) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.unsigned_abs();
+    format!("{sign}{}.{:02} {currency}", cents / 100, cents % 100)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Billing;
+    use crate::config::Config;
+    use crate::model::Entry;
+    use chrono::{Duration, NaiveDateTime};
+    use figment::Figment;
+    use figment::providers::{Format, Toml};
+
+    fn entry(message: &str) -> Entry {
+        Entry {
+            id: 0,
+            timestamp: NaiveDateTime::parse_from_str("2025-07-24 09:00", "%Y-%m-%d %H:%M").unwrap(),
+            message: message.to_string(),
+            long: None,
+        }
+    }
+
+    #[test]
+    fn billing_rules() {
+        let config: Config = Figment::new()
+            .merge(Toml::string(
+                r#"
+                [[projects]]
+                name = "Acme support"
+                tag = "support"
+                billable = false
+
+                [[projects]]
+                name = "Acme website"
+                tag = "web"
+                rate = 100
+
+                [[clients]]
+                name = "Acme"
+                message = "(?i)acme"
+                rate = 80
+                "#,
+            ))
+            .extract()
+            .unwrap();
+        let billing = |message| Billing::of(&entry(message), &config).unwrap();
+
+        assert_eq!(Some(80.0), billing("Acme login").rate);
+        assert!(billing("Acme login").billable);
+        assert!(!billing("Acme #support").billable);
+        assert!(billing("Acme #support #billable").billable);
+        assert!(!billing("Acme login #NonBillable").billable);
+        assert_eq!(Some(100.0), billing("Acme #web").rate);
+        assert_eq!(Some(100.0), billing("#web").rate);
+        assert!(!billing("mails").billable);
+        assert_eq!(None, billing("mails").rate);
+
+        assert_eq!(12000, billing("Acme #web").amount(Duration::minutes(72)));
+        assert_eq!(0, billing("Acme #support").amount(Duration::hours(1)));
+        assert_eq!("1234.05 EUR", super::format_amount(123405, "EUR"));
+        assert_eq!("-0.05 EUR", super::format_amount(-5, "EUR"));
+        assert_eq!("-12.30 EUR", super::format_amount(-1230, "EUR"));
+    }
+}
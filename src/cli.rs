@@ -0,0 +1,136 @@
+use crate::clock;
+use crate::commands::*;
+use crate::config::Config;
+use crate::db::Database;
+use crate::migration;
+use crate::storage::MemoryStorage;
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use clap::Parser;
+use std::env;
+use yansi::Paint;
+
+/// Simple tool to do time tracking
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Path to a config file
+    #[arg(short, long)]
+    config: Option<String>,
+
+    /// Allow changing entries of locked periods
+    #[arg(long, global = true)]
+    force: bool,
+
+    /// Run the command on an in-memory copy of the entries, the trash, locks
+    /// and the undo log without saving any changes
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Run as if it was the given time, e.g. `2025-07-24T09:00`; mainly
+    /// for testing. Overrides the `TRACKER_NOW` environment variable.
+    #[arg(long = "now", global = true, hide = true, value_parser = clock::parse_now)]
+    _now: Option<NaiveDateTime>, // read by `now_arg` before parsing
+
+    #[command(subcommand)]
+    commands: Commands,
+}
+
+crate::register_commands! {
+    Add
+    View
+    Delete
+    Edit
+    Insert
+    Undo
+    Redo
+    Trash
+    History
+    Seal
+    Verify
+    Lock
+    Unlock
+    Backup [database]
+    Restore [database]
+    Db [database]
+    Export
+    Import
+    Timesheet
+    Invoice
+    Report
+}
+
+/// Parses the command line arguments and runs the given command. Built with
+/// the `clap-markdown` feature, it prints the help of all commands as
+/// markdown instead.
+#[cfg_attr(feature = "clap-markdown", allow(unreachable_code))]
+pub fn run() -> Result<()> {
+    #[cfg(feature = "clap-markdown")]
+    {
+        clap_markdown::print_help_markdown::<Cli>();
+        return Ok(());
+    }
+
+    // Relative dates are resolved against the clock while the arguments are
+    // parsed, so the clock has to be set before.
+    let now = match now_arg() {
+        Some(now) => Some(clock::parse_now(&now)?),
+        None => clock::now_from_env()?,
+    };
+    if let Some(now) = now {
+        clock::set_now(now)?;
+    }
+
+    let cli = Cli::parse();
+    let config = Config::parse(cli.config)?;
+
+    migration::migrate(&config)?;
+
+    let db = Database::new(&config.storage_dir)?
+        .with_force(cli.force)
+        .with_backup_retention(config.backup_retention.as_ref().map(|r| r.duration()));
+
+    // A dry run must not write anything, not even backups.
+    if config.daily_backup && !cli.dry_run {
+        db.backup_daily()?;
+    }
+    if let (Some(retention), AnyCommand::Storage(command)) =
+        (&config.trash_retention, cli.commands.command())
+        && command.writes()
+        && !cli.dry_run
+    {
+        db.purge_expired(retention.duration())?;
+    }
+
+    match cli.commands.command() {
+        AnyCommand::Database(_) if cli.dry_run => Err(anyhow::anyhow!(
+            "the command works on the database file and can not be run with --dry-run"
+        )),
+        AnyCommand::Database(command) => command.run(&db, &config),
+        AnyCommand::Storage(command) if cli.dry_run => {
+            let memory = MemoryStorage::copy_of(&db)?.with_force(cli.force);
+            command.run(&memory, &config)?;
+            println!("{}", "Dry run: no changes have been saved.".italic().dim());
+            Ok(())
+        }
+        AnyCommand::Storage(command) => command.run(&db, &config),
+    }
+}
+
+/// Returns the value of the `--now` argument, which is read before the
+/// other arguments are parsed.
+fn now_arg() -> Option<String> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--" => break,
+            "--now" => return args.next(),
+            _ => {
+                if let Some(now) = arg.strip_prefix("--now=") {
+                    return Some(now.to_string());
+                }
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use std::env;
+use std::sync::OnceLock;
+
+/// Environment variable to fix the current time with, e.g.
+/// `TRACKER_NOW=2025-07-24T09:00`.
+pub const NOW_VAR: &str = "TRACKER_NOW";
+
+const FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+
+static NOW: OnceLock<NaiveDateTime> = OnceLock::new();
+
+/// Fixes the time returned by [`now`] for the rest of the process. Fails if
+/// the time has already been fixed.
+pub fn set_now(now: NaiveDateTime) -> Result<()> {
+    NOW.set(now)
+        .map_err(|_| anyhow::anyhow!("the current time has already been set"))
+}
+
+/// Returns the time set via [`NOW_VAR`], if any.
+pub fn now_from_env() -> Result<Option<NaiveDateTime>> {
+    match env::var(NOW_VAR) {
+        Ok(v) => parse_now(&v)
+            .map(Some)
+            .map_err(|err| anyhow::anyhow!("invalid {NOW_VAR}: {err}")),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Parses a point in time like `2025-07-24T09:00` or `2025-07-24 09:00:30`.
+/// A date alone refers to its start.
+pub fn parse_now(s: &str) -> Result<NaiveDateTime> {
+    FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(s, f).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+        .ok_or_else(|| anyhow::anyhow!("'{s}' is not a valid point in time"))
+}
+
+/// Returns the current local time, unless it has been fixed via
+/// [`set_now`].
+pub fn now() -> NaiveDateTime {
+    NOW.get()
+        .copied()
+        .unwrap_or_else(|| Local::now().naive_local())
+}
+
+/// Returns the current local date, see [`now`].
+pub fn today() -> NaiveDate {
+    now().date()
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_now;
+
+    #[test]
+    fn parse() {
+        let expected = |s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(
+            expected("2025-07-24 09:00:00"),
+            parse_now("2025-07-24T09:00").unwrap()
+        );
+        assert_eq!(
+            expected("2025-07-24 09:00:30"),
+            parse_now("2025-07-24 09:00:30").unwrap()
+        );
+        assert_eq!(
+            expected("2025-07-24 00:00:00"),
+            parse_now("2025-07-24").unwrap()
+        );
+        assert!(parse_now("09:00").is_err());
+    }
+}
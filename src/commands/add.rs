@@ -1,10 +1,12 @@
 use super::Command;
+use crate::billing::{BILLABLE_TAG, NON_BILLABLE_TAG};
+use crate::clock;
 use crate::config::Config;
-use crate::db::Database;
-use crate::model::NewEntry;
+use crate::model::{Change, NewEntry};
+use crate::storage::Storage;
 use crate::util::{Parsable, select_date};
 use anyhow::Result;
-use chrono::{DurationRound, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use clap::Args;
 
 /// Add a track entry
@@ -33,10 +35,18 @@ pub struct Add {
     /// Add a long description as text content
     #[arg(long)]
     long_text: Option<String>,
+
+    /// Mark the entry as billable by tagging it with `#billable`
+    #[arg(long, conflicts_with = "non_billable")]
+    billable: bool,
+
+    /// Mark the entry as not billable by tagging it with `#nonbillable`
+    #[arg(long)]
+    non_billable: bool,
 }
 
 impl Command for Add {
-    fn run(&self, db: &Database, config: &Config) -> Result<()> {
+    fn run(&self, db: &dyn Storage, _config: &Config) -> Result<()> {
         if self.message.is_empty() {
             return Err(anyhow::anyhow!("can not use empty message value"));
         }
@@ -44,10 +54,10 @@ impl Command for Add {
         let date = match self.date {
             Some(Parsable(date_str)) => date_str,
             None if self.select => select_date()?,
-            _ => Local::now().date_naive(),
+            _ => clock::today(),
         };
 
-        let now = Local::now().naive_local();
+        let now = clock::now();
         let timestamp = match self.time {
             Some(ref time) => NaiveDateTime::new(date, NaiveTime::parse_from_str(time, "%H:%M")?),
             None => now,
@@ -61,16 +71,24 @@ impl Command for Add {
             },
         };
 
-        let timestamp = match config.round_steps {
-            Some(ref round) => timestamp.duration_round(round.duration())?,
-            None => timestamp,
-        };
+        let mut message = self.message.join(" ");
+        if self.billable {
+            message += &format!(" #{BILLABLE_TAG}");
+        } else if self.non_billable {
+            message += &format!(" #{NON_BILLABLE_TAG}");
+        }
 
-        db.add(NewEntry {
+        let entry = NewEntry {
             timestamp,
-            message: self.message.join(" "),
+            message,
             long,
-        })
+        };
+
+        db.apply(&format!("add {}", entry.summary()), vec![Change::Add(entry)])
+    }
+
+    fn writes(&self) -> bool {
+        true
     }
 }
 
@@ -0,0 +1,43 @@
+use super::DatabaseCommand;
+use crate::config::Config;
+use crate::db::Database;
+use anyhow::Result;
+use clap::Args;
+use yansi::Paint;
+
+/// Create a snapshot of the database or list existing snapshots
+#[derive(Args)]
+pub struct Backup {
+    /// List all backups, latest first
+    #[arg(short, long)]
+    list: bool,
+}
+
+impl DatabaseCommand for Backup {
+    fn run(&self, db: &Database, _config: &Config) -> Result<()> {
+        if self.list {
+            let backups = db.backups()?;
+            if backups.is_empty() {
+                println!("{}", "No backups have been taken yet.".italic().dim());
+            }
+            for backup in backups {
+                println!(
+                    "{} {} {}",
+                    backup.name(),
+                    format!("{:<9}", backup.reason).cyan(),
+                    format!("{} KiB", backup.size.div_ceil(1024)).dim()
+                );
+            }
+            return Ok(());
+        }
+
+        let backup = db.backup("manual")?;
+        println!(
+            "{} {}",
+            "Created backup".green(),
+            backup.path.display().dim()
+        );
+
+        Ok(())
+    }
+}
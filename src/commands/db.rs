@@ -0,0 +1,98 @@
+use super::DatabaseCommand;
+use crate::config::Config;
+use crate::db::Database;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use yansi::Paint;
+
+/// Inspect and maintain the database
+#[derive(Args)]
+pub struct Db {
+    #[command(subcommand)]
+    action: DbAction,
+}
+
+#[derive(Subcommand)]
+enum DbAction {
+    /// Check the database file and the stored entries for problems
+    Check,
+
+    /// Rebuild the database file to reclaim unused space
+    Vacuum,
+
+    /// Show statistics about the stored entries and the database file
+    Stats,
+
+    /// Print the path of the database file
+    Path,
+}
+
+impl DatabaseCommand for Db {
+    fn run(&self, db: &Database, _config: &Config) -> Result<()> {
+        match self.action {
+            DbAction::Check => check(db),
+            DbAction::Vacuum => vacuum(db),
+            DbAction::Stats => stats(db),
+            DbAction::Path => {
+                println!("{}", db.path().display());
+                Ok(())
+            }
+        }
+    }
+}
+
+fn check(db: &Database) -> Result<()> {
+    let integrity = db.integrity_check()?;
+    for problem in &integrity {
+        println!("{} {}", "database:".red(), problem);
+    }
+
+    let entries = db.check_entries()?;
+    for problem in &entries {
+        println!(
+            "{} {}",
+            format!("#{}:", problem.entry_id).red(),
+            problem.problems.join(", ")
+        );
+    }
+
+    let count = integrity.len() + entries.len();
+    if count > 0 {
+        return Err(anyhow::anyhow!("found {count} problems"));
+    }
+
+    println!("{}", "No problems found.".green());
+    Ok(())
+}
+
+fn vacuum(db: &Database) -> Result<()> {
+    db.backup("vacuum")?;
+    let (before, after) = db.vacuum()?;
+    println!(
+        "{}",
+        format!("Vacuumed the database from {} to {}.", kib(before), kib(after)).green()
+    );
+    Ok(())
+}
+
+fn stats(db: &Database) -> Result<()> {
+    let stats = db.stats()?;
+
+    let range = match (stats.first, stats.last) {
+        (Some(first), Some(last)) => format!("{first} to {last}"),
+        _ => "-".to_string(),
+    };
+
+    println!("{:<15} {}", "Entries:".bold(), stats.entries);
+    println!("{:<15} {}", "In trash:".bold(), stats.deleted);
+    println!("{:<15} {}", "Date range:".bold(), range);
+    println!("{:<15} {}", "File size:".bold(), kib(stats.file_size));
+    println!("{:<15} {}", "Schema version:".bold(), stats.schema_version);
+    println!("{:<15} {}", "Path:".bold(), db.path().display());
+
+    Ok(())
+}
+
+fn kib(bytes: u64) -> String {
+    format!("{} KiB", bytes.div_ceil(1024))
+}
@@ -1,9 +1,12 @@
 use super::Command;
+use crate::clock;
 use crate::config::Config;
-use crate::db::Database;
-use crate::util::{FormatableEntry, Parsable, select_date};
+use crate::model::{Change, Entry};
+use crate::storage::Storage;
+use crate::util::{
+    DateRange, FormatableEntry, Parsable, ensure_interactive, entry_by_index, select_date,
+};
 use anyhow::Result;
-use chrono::{Local, NaiveDate};
 use clap::Args;
 use inquire::MultiSelect;
 use yansi::Paint;
@@ -12,46 +15,97 @@ use yansi::Paint;
 #[derive(Args)]
 #[command(visible_aliases = ["d"])]
 pub struct Delete {
-    /// Date of the list
-    date: Option<Parsable<NaiveDate>>,
+    /// Date or date range of the list
+    date: Option<Parsable<DateRange>>,
 
     /// Select date from an interactive calender
     #[arg(short, long)]
     select: bool,
+
+    /// Delete the entries with the given IDs (see `view --ids`)
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["date", "select", "index"])]
+    id: Vec<u32>,
+
+    /// Delete the entries with the given indexes of the list without
+    /// prompting
+    #[arg(short = 'n', long, value_delimiter = ',')]
+    index: Vec<usize>,
 }
 
 impl Command for Delete {
-    fn run(&self, db: &Database, config: &Config) -> Result<()> {
-        let date = match self.date {
-            Some(Parsable(date_str)) => date_str,
-            None if self.select => select_date()?,
-            _ => Local::now().date_naive(),
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()> {
+        if !self.id.is_empty() {
+            let selected = self
+                .id
+                .iter()
+                .map(|&id| {
+                    db.get(id)?
+                        .ok_or_else(|| anyhow::anyhow!("no entry with id {id}"))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            return delete(db, &selected.iter().collect::<Vec<_>>());
+        }
+
+        let range = match self.date {
+            Some(Parsable(range)) => range,
+            None if self.select => DateRange::day(select_date()?),
+            _ => DateRange::day(clock::today()),
         };
 
-        let mut entries = db.list(date)?;
+        let entries = db.list_range(range.start, range.end)?;
 
         if entries.is_empty() {
             println!("{}", "There are no entries for this day.".italic().dim());
             return Ok(());
         }
 
-        entries.sort_by_key(|e| e.timestamp);
+        let selected: Vec<&Entry> = match self.index.is_empty() {
+            true => {
+                ensure_interactive("pass the indexes or --id of the entries to delete")?;
 
-        let select_entries: Vec<_> = entries
-            .iter()
-            .map(|e| FormatableEntry::new(e, config, false))
-            .collect();
+                let select_entries: Vec<_> = entries
+                    .iter()
+                    .map(|e| {
+                        FormatableEntry::new(e, config, false).with_date(!range.is_single_day())
+                    })
+                    .collect();
 
-        let selected: Vec<_> = MultiSelect::new("Select entries to delete", select_entries)
-            .prompt()?
-            .into_iter()
-            .map(|e| e.entry)
-            .collect();
+                MultiSelect::new("Select entries to delete", select_entries)
+                    .prompt()?
+                    .into_iter()
+                    .map(|e| e.entry)
+                    .collect()
+            }
+            false => {
+                if !range.is_single_day() {
+                    return Err(anyhow::anyhow!(
+                        "entry indexes can only be used with a single date"
+                    ));
+                }
+                self.index
+                    .iter()
+                    .map(|&i| entry_by_index(&entries, i))
+                    .collect::<Result<_>>()?
+            }
+        };
 
-        for e in selected {
-            db.delete(e.id)?;
-        }
+        delete(db, &selected)
+    }
 
-        Ok(())
+    fn writes(&self) -> bool {
+        true
     }
 }
+
+fn delete(db: &dyn Storage, entries: &[&Entry]) -> Result<()> {
+    let description = match entries {
+        [] => return Ok(()),
+        [entry] => format!("delete {}", entry.summary()),
+        _ => format!("delete {} entries", entries.len()),
+    };
+
+    db.apply(
+        &description,
+        entries.iter().map(|e| Change::Delete(e.id)).collect(),
+    )
+}
@@ -1,10 +1,12 @@
 use super::Command;
+use crate::clock;
 use crate::config::Config;
-use crate::db::Database;
-use crate::model::Entry;
-use crate::util::{FormatableEntry, Parsable, select_date};
+use crate::model::{Change, Entry};
+use crate::storage::Storage;
+use crate::text;
+use crate::util::{FormatableEntry, Parsable, ensure_interactive, entry_by_index, select_date};
 use anyhow::Result;
-use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use clap::Args;
 use inquire::{CustomType, Editor, Select, Text};
 use yansi::Paint;
@@ -23,32 +25,133 @@ pub struct Edit {
     /// Edit the latest added entry
     #[arg(short, long)]
     last: bool,
+
+    /// Edit the entry with the given ID (see `view --ids`)
+    #[arg(long, conflicts_with_all = ["date", "select", "last", "index"])]
+    id: Option<u32>,
+
+    /// Edit the entry with the given index of the list
+    #[arg(short = 'n', long, conflicts_with = "last")]
+    index: Option<usize>,
+
+    /// Set the time of the entry without prompting
+    #[arg(short, long)]
+    time: Option<String>,
+
+    /// Set the message of the entry without prompting
+    #[arg(short, long)]
+    message: Option<String>,
+
+    /// Set the long description of the entry without prompting
+    #[arg(long)]
+    long_text: Option<String>,
+
+    /// Edit all entries of the day as text in an editor
+    #[arg(
+        long,
+        conflicts_with_all = ["last", "id", "index", "time", "message", "long_text"]
+    )]
+    text: bool,
 }
 
 impl Command for Edit {
-    fn run(&self, db: &Database, config: &Config) -> Result<()> {
-        let date = match self.date {
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()> {
+        if self.text {
+            return self.edit_text(db);
+        }
+
+        let selected = match self.id {
+            Some(id) => db
+                .get(id)?
+                .ok_or_else(|| anyhow::anyhow!("no entry with id {id}"))?,
+            None => match self.select_entry(db, config)? {
+                Some(entry) => entry,
+                None => return Ok(()),
+            },
+        };
+
+        let selected_summary = selected.summary();
+
+        let new = match self.time.is_some() || self.message.is_some() || self.long_text.is_some()
+        {
+            true => self.apply_args(selected)?,
+            false => {
+                ensure_interactive("use --time, --message or --long-text to edit the entry")?;
+                prompt_entry(selected)?
+            }
+        };
+
+        db.apply(
+            &format!("edit {}", selected_summary),
+            vec![Change::Update(new)],
+        )
+    }
+
+    fn writes(&self) -> bool {
+        true
+    }
+}
+
+impl Edit {
+    fn date(&self) -> Result<NaiveDate> {
+        Ok(match self.date {
             Some(Parsable(date_str)) => date_str,
             None if self.select => select_date()?,
-            _ => Local::now().date_naive(),
-        };
+            _ => clock::today(),
+        })
+    }
+
+    fn edit_text(&self, db: &dyn Storage) -> Result<()> {
+        let date = self.date()?;
 
         let mut entries = db.list(date)?;
+        entries.sort_by_key(|e| e.timestamp);
+
+        let mut content = text::format_entries(&entries);
+
+        let new_entries = loop {
+            let edited = edit::edit(&content)?;
+            match text::parse_entries(&edited) {
+                Ok(v) => break v,
+                Err(_) if edited == content => {
+                    return Err(anyhow::anyhow!("aborted editing due to unresolved errors"));
+                }
+                Err(err) => content = text::with_error(&edited, &err),
+            }
+        };
 
-        if entries.is_empty() {
-            println!("{}", "There are no entries for this day.".italic().dim());
+        let changes = text::diff(date, &entries, &new_entries);
+        if changes.is_empty() {
+            println!("{}", "No changes have been made.".italic().dim());
             return Ok(());
         }
 
+        db.apply(
+            &format!("edit {date} as text ({} changes)", changes.len()),
+            changes,
+        )
+    }
+
+    fn select_entry(&self, db: &dyn Storage, config: &Config) -> Result<Option<Entry>> {
+        let date = self.date()?;
+
+        let mut entries = db.list(date)?;
+
         if entries.is_empty() {
-            return Err(anyhow::anyhow!("There are no entreis for the given date."));
+            println!("{}", "There are no entries for this day.".italic().dim());
+            return Ok(None);
         }
 
-        let selected = match self.last {
-            true => entries
+        let selected = match (self.last, self.index) {
+            (true, _) => entries
                 .last()
                 .ok_or_else(|| anyhow::anyhow!("no entries found"))?,
-            false => {
+            (false, Some(index)) => {
+                entries.sort_by_key(|e| e.timestamp);
+                entry_by_index(&entries, index)?
+            }
+            (false, None) => {
+                ensure_interactive("use --id, --index or --last to select an entry")?;
                 entries.sort_by_key(|e| e.timestamp);
                 let entries: Vec<_> = entries
                     .iter()
@@ -58,38 +161,72 @@ impl Command for Edit {
             }
         };
 
-        let time: NaiveTime = CustomType::new("Time")
-            .with_parser(&parse_time)
-            .with_formatter(&format_time)
-            .with_default_value_formatter(&format_time)
-            .with_starting_input(&format_time(selected.timestamp.time()))
-            .with_error_message("Invalid value. Must be time i nformat %H:%M")
-            .prompt()?;
-
-        let timestamp = NaiveDateTime::new(selected.timestamp.date(), time);
+        Ok(Some(selected.clone()))
+    }
 
-        let message = Text::new("Message")
-            .with_initial_value(&selected.message)
-            .prompt()?;
+    fn apply_args(&self, selected: Entry) -> Result<Entry> {
+        let timestamp = match self.time {
+            Some(ref time) => NaiveDateTime::new(
+                selected.timestamp.date(),
+                NaiveTime::parse_from_str(time, "%H:%M")?,
+            ),
+            None => selected.timestamp,
+        };
 
-        let long = Editor::new("Long")
-            .with_predefined_text(&selected.long.clone().unwrap_or_default())
-            .prompt()?;
+        let message = match self.message {
+            Some(ref message) if message.trim().is_empty() => {
+                return Err(anyhow::anyhow!("can not use empty message value"));
+            }
+            Some(ref message) => message.clone(),
+            None => selected.message,
+        };
 
-        let long = long.trim();
-        let long = match long.is_empty() {
-            true => None,
-            false => Some(long.to_string()),
+        let long = match self.long_text {
+            Some(ref long) => non_empty(long),
+            None => selected.long,
         };
 
-        let new: Entry = Entry {
+        Ok(Entry {
             id: selected.id,
             timestamp,
             message,
             long,
-        };
+        })
+    }
+}
+
+fn prompt_entry(selected: Entry) -> Result<Entry> {
+    let time: NaiveTime = CustomType::new("Time")
+        .with_parser(&parse_time)
+        .with_formatter(&format_time)
+        .with_default_value_formatter(&format_time)
+        .with_starting_input(&format_time(selected.timestamp.time()))
+        .with_error_message("Invalid value. Must be time i nformat %H:%M")
+        .prompt()?;
+
+    let timestamp = NaiveDateTime::new(selected.timestamp.date(), time);
+
+    let message = Text::new("Message")
+        .with_initial_value(&selected.message)
+        .prompt()?;
+
+    let long = Editor::new("Long")
+        .with_predefined_text(&selected.long.clone().unwrap_or_default())
+        .prompt()?;
+
+    Ok(Entry {
+        id: selected.id,
+        timestamp,
+        message,
+        long: non_empty(&long),
+    })
+}
 
-        db.update(new)
+fn non_empty(long: &str) -> Option<String> {
+    let long = long.trim();
+    match long.is_empty() {
+        true => None,
+        false => Some(long.to_string()),
     }
 }
 
@@ -0,0 +1,84 @@
+use super::Command;
+use crate::clock;
+use crate::config::Config;
+use crate::export;
+use crate::storage::Storage;
+use crate::util::{DateRange, Parsable};
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use yansi::Paint;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// iCalendar events, one per tracked interval
+    Ics,
+    /// JSON as read by `timew import`, with breaks left out
+    Timew,
+    /// Timeclock entries for ledger and hledger
+    Ledger,
+    /// One org-mode heading per day with `CLOCK:` lines
+    Org,
+    /// One Markdown table per day
+    #[value(alias = "md")]
+    Markdown,
+    /// Excel workbook with a sheet per month and weekly totals, requires
+    /// `--output`
+    Xlsx,
+}
+
+/// Export tracked entries into other formats
+#[derive(Args)]
+pub struct Export {
+    /// Date or date range to export
+    date: Option<Parsable<DateRange>>,
+
+    /// Format to export to
+    #[arg(short, long)]
+    format: Format,
+
+    /// File to write to instead of the standard output
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Leave out intervals ending with a break entry (ics only, breaks are
+    /// always left out for timew)
+    #[arg(long)]
+    exclude_breaks: bool,
+}
+
+impl Command for Export {
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()> {
+        let range = match self.date {
+            Some(Parsable(range)) => range,
+            None => DateRange::day(clock::today()),
+        };
+        if matches!(self.format, Format::Xlsx) && self.output.is_none() {
+            return Err(anyhow::anyhow!("the xlsx format requires --output"));
+        }
+        let entries = db.list_range(range.start, range.end)?;
+
+        let mut w: Box<dyn Write> = match self.output {
+            Some(ref path) => Box::new(BufWriter::new(File::create(path)?)),
+            None => Box::new(io::stdout().lock()),
+        };
+
+        match self.format {
+            Format::Ics => export::write_ics(&mut w, &entries, config, !self.exclude_breaks)?,
+            Format::Timew => export::write_timew(&mut w, &entries, config)?,
+            Format::Ledger => export::write_ledger(&mut w, &entries, config)?,
+            Format::Org => export::write_org(&mut w, &entries, config)?,
+            Format::Markdown => export::write_markdown(&mut w, &entries, config)?,
+            Format::Xlsx => export::write_xlsx(&mut w, &entries, config)?,
+        }
+        w.flush()?;
+
+        if let Some(ref path) = self.output {
+            println!("{} {}", "Exported to".green(), path.display().dim());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+use super::Command;
+use crate::clock;
+use crate::config::Config;
+use crate::db::{HistoryAction, HistoryEntry};
+use crate::model::Entry;
+use crate::storage::Storage;
+use crate::util::{DateRange, Parsable};
+use anyhow::Result;
+use clap::Args;
+use yansi::Paint;
+
+/// Show all recorded changes of entries
+#[derive(Args)]
+pub struct History {
+    /// Date or date range of the entries to show the history of
+    date: Option<Parsable<DateRange>>,
+
+    /// Show the history of the entry with the given ID (see `view --ids`)
+    #[arg(long, conflicts_with_all = ["date", "changed"])]
+    id: Option<u32>,
+
+    /// Select changes made within the date range instead of changes of
+    /// entries dated within the range
+    #[arg(short, long)]
+    changed: bool,
+}
+
+impl Command for History {
+    fn run(&self, db: &dyn Storage, _config: &Config) -> Result<()> {
+        if let Some(id) = self.id {
+            let history = db.history(id)?;
+            if history.is_empty() {
+                return Err(anyhow::anyhow!("no history for entry with id {id}"));
+            }
+            return print_history(db, id, &history, None);
+        }
+
+        let range = match self.date {
+            Some(Parsable(range)) => range,
+            None => DateRange::day(clock::today()),
+        };
+
+        let ids = db.history_ids(range.start, range.end, self.changed)?;
+        if ids.is_empty() {
+            println!("{}", "There are no changes in this range.".italic().dim());
+            return Ok(());
+        }
+
+        let filter = self.changed.then_some(range);
+        for id in ids {
+            print_history(db, id, &db.history(id)?, filter)?;
+            println!();
+        }
+
+        Ok(())
+    }
+}
+
+fn print_history(
+    db: &dyn Storage,
+    id: u32,
+    history: &[HistoryEntry],
+    changed_within: Option<DateRange>,
+) -> Result<()> {
+    let Some(last) = history.last() else {
+        return Ok(());
+    };
+
+    print!("{} {}", format!("#{id}").bold(), last.entry.summary().bold());
+    if let Some((created_at, updated_at)) = db.entry_times(id)? {
+        if let Some(created_at) = created_at {
+            print!(" {}", format!("created {}", created_at.format("%Y-%m-%d %H:%M")).dim());
+        }
+        if let Some(updated_at) = updated_at {
+            print!(" {}", format!("updated {}", updated_at.format("%Y-%m-%d %H:%M")).dim());
+        }
+    }
+    println!();
+
+    let mut previous: Option<&Entry> = None;
+    for version in history {
+        let visible = changed_within.is_none_or(|r| {
+            let date = version.changed_at.date();
+            r.start <= date && date <= r.end
+        });
+
+        if visible {
+            let lines = match (version.action, previous) {
+                (HistoryAction::Update, Some(previous)) => {
+                    let lines = diff_entries(previous, &version.entry);
+                    match lines.is_empty() {
+                        true => vec!["no changes".dim().to_string()],
+                        false => lines,
+                    }
+                }
+                (HistoryAction::Restore, Some(previous)) => diff_entries(previous, &version.entry),
+                (HistoryAction::Delete | HistoryAction::Purge, _) => vec![],
+                _ => vec![format!(
+                    "{} {}",
+                    version.entry.timestamp.format("%Y-%m-%d %H:%M"),
+                    version.entry.message
+                )],
+            };
+
+            let mut lines = lines.into_iter();
+            let first = lines.next().unwrap_or_default();
+            println!(
+                "  {}  {:<8} {}{}",
+                version.changed_at.format("%Y-%m-%d %H:%M:%S").dim(),
+                format!("{:<8}", version.action).cyan(),
+                first,
+                match version.forced {
+                    true => format!(" {}", "(forced)".yellow()),
+                    false => String::new(),
+                }
+            );
+            for line in lines {
+                println!("  {:<19}  {:<8} {}", "", "", line);
+            }
+        }
+
+        previous = Some(&version.entry);
+    }
+
+    Ok(())
+}
+
+/// Returns a line for each changed field between the two versions of
+/// an entry.
+fn diff_entries(old: &Entry, new: &Entry) -> Vec<String> {
+    let mut lines = vec![];
+
+    if old.timestamp != new.timestamp {
+        let format = match old.timestamp.date() == new.timestamp.date() {
+            true => "%H:%M",
+            false => "%Y-%m-%d %H:%M",
+        };
+        lines.push(format!(
+            "time: {} {} {}",
+            old.timestamp.format(format).red(),
+            "→".dim(),
+            new.timestamp.format(format).green()
+        ));
+    }
+
+    if old.message != new.message {
+        lines.push(format!(
+            "message: {} {} {}",
+            format!("'{}'", old.message).red(),
+            "→".dim(),
+            format!("'{}'", new.message).green()
+        ));
+    }
+
+    if old.long != new.long {
+        let old: Vec<_> = old.long.as_deref().unwrap_or_default().lines().collect();
+        let new: Vec<_> = new.long.as_deref().unwrap_or_default().lines().collect();
+
+        // Lines shared at the start and end are shown as unchanged; all
+        // lines in between as removed and added.
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        lines.push("long:".to_string());
+        for line in &old[..prefix] {
+            lines.push(format!("    {line}").dim().to_string());
+        }
+        for line in &old[prefix..old.len() - suffix] {
+            lines.push(format!("  - {line}").red().to_string());
+        }
+        for line in &new[prefix..new.len() - suffix] {
+            lines.push(format!("  + {line}").green().to_string());
+        }
+        for line in &old[old.len() - suffix..] {
+            lines.push(format!("    {line}").dim().to_string());
+        }
+    }
+
+    lines
+}
@@ -0,0 +1,147 @@
+use super::Command;
+use crate::clock;
+use crate::config::Config;
+use crate::import;
+use crate::model::{Change, Entry, NewEntry};
+use crate::storage::Storage;
+use crate::util::{DateRange, FormatableEntry, Parsable, ensure_interactive};
+use anyhow::Result;
+use chrono::NaiveDate;
+use clap::{ArgGroup, Args};
+use inquire::MultiSelect;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use yansi::Paint;
+
+/// Import entries from other tools
+#[derive(Args)]
+#[command(group(ArgGroup::new("source").required(true)))]
+pub struct Import {
+    /// iCalendar file to import events from, e.g. meetings
+    #[arg(long, group = "source")]
+    ics: Option<PathBuf>,
+
+    /// JSON file written by `timew export`, or `-` to read it from stdin
+    #[arg(long, group = "source")]
+    timew: Option<PathBuf>,
+
+    /// Date or date range to import [default: all dates of the input]
+    #[arg(short, long)]
+    date: Option<Parsable<DateRange>>,
+
+    /// Import all proposed entries without review
+    #[arg(short, long)]
+    yes: bool,
+}
+
+impl Command for Import {
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()> {
+        let (source, proposed, range) = match (&self.ics, &self.timew) {
+            (Some(path), _) => {
+                let events = import::read_ics(&read(path)?)?;
+                let range = self.range(events.iter().map(|e| e.start.date()))?;
+                let events: Vec<_> = events
+                    .into_iter()
+                    .filter(|e| range.contains(e.start.date()))
+                    .collect();
+                let existing = db.list_range(range.start, range.end)?;
+                (path, import::entries_for_events(&events, &existing), range)
+            }
+            (_, Some(path)) => {
+                let mut entries = import::read_timew(&read(path)?, config)?;
+                let range = self.range(entries.iter().map(|e| e.timestamp.date()))?;
+                let existing = db.list_range(range.start, range.end)?;
+                entries.retain(|e| {
+                    range.contains(e.timestamp.date())
+                        && !existing
+                            .iter()
+                            .any(|x| x.timestamp == e.timestamp && x.message == e.message)
+                });
+                (path, entries, range)
+            }
+            (None, None) => unreachable!("a source is required"),
+        };
+
+        if proposed.is_empty() {
+            println!(
+                "{}",
+                format!("Imported 0 entries, as there is nothing new to import in {range}.")
+                    .yellow()
+            );
+            return Ok(());
+        }
+
+        let selected = match self.yes {
+            true => proposed,
+            false => review(config, proposed)?,
+        };
+        if selected.is_empty() {
+            println!("{}", "No entries have been imported.".italic().dim());
+            return Ok(());
+        }
+
+        let count = selected.len();
+        db.apply(
+            &format!("import {count} entries from {}", source.display()),
+            selected.into_iter().map(Change::Add).collect(),
+        )?;
+        println!("{}", format!("Imported {count} entries.").green());
+
+        Ok(())
+    }
+
+    fn writes(&self) -> bool {
+        true
+    }
+}
+
+impl Import {
+    /// Returns the range to import, which defaults to all given dates of the
+    /// input.
+    fn range(&self, dates: impl Iterator<Item = NaiveDate>) -> Result<DateRange> {
+        if let Some(Parsable(range)) = self.date {
+            return Ok(range);
+        }
+        let dates: Vec<_> = dates.collect();
+        match (dates.iter().min(), dates.iter().max()) {
+            (Some(&start), Some(&end)) => DateRange::new(start, end),
+            _ => Ok(DateRange::day(clock::today())),
+        }
+    }
+}
+
+/// Reads the given file, or stdin for `-`.
+fn read(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        return Ok(io::read_to_string(io::stdin())?);
+    }
+    Ok(fs::read_to_string(path)?)
+}
+
+/// Lets the user pick the proposed entries to import.
+fn review(config: &Config, proposed: Vec<NewEntry>) -> Result<Vec<NewEntry>> {
+    ensure_interactive("pass --yes to import all proposed entries")?;
+
+    let entries: Vec<_> = proposed
+        .iter()
+        .map(|e| Entry {
+            id: 0,
+            timestamp: e.timestamp,
+            message: e.message.clone(),
+            long: e.long.clone(),
+        })
+        .collect();
+    let options: Vec<_> = entries
+        .iter()
+        .map(|e| FormatableEntry::new(e, config, false).with_date(true))
+        .collect();
+
+    let selected = MultiSelect::new("Select entries to import", options)
+        .with_all_selected_by_default()
+        .raw_prompt()?;
+    Ok(selected
+        .into_iter()
+        .map(|o| proposed[o.index].clone())
+        .collect())
+}
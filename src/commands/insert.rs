@@ -1,10 +1,11 @@
 use super::Command;
+use crate::clock;
 use crate::config::Config;
-use crate::db::Database;
-use crate::model::{Entry, NewEntry};
+use crate::model::{Change, Entry, NewEntry};
+use crate::storage::Storage;
 use crate::util::{Parsable, select_date};
 use anyhow::Result;
-use chrono::{DurationRound, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use clap::Args;
 
 /// Swaps the next entry with the given timestamp and sets the next
@@ -37,7 +38,7 @@ pub struct Insert {
 }
 
 impl Command for Insert {
-    fn run(&self, db: &Database, config: &Config) -> Result<()> {
+    fn run(&self, db: &dyn Storage, _config: &Config) -> Result<()> {
         if self.message.is_empty() {
             return Err(anyhow::anyhow!("can not use empty message value"));
         }
@@ -45,10 +46,10 @@ impl Command for Insert {
         let date = match self.date {
             Some(Parsable(date_str)) => date_str,
             None if self.select => select_date()?,
-            _ => Local::now().date_naive(),
+            _ => clock::today(),
         };
 
-        let now = Local::now().naive_local();
+        let now = clock::now();
         let timestamp = match self.time {
             Some(ref time) => NaiveDateTime::new(date, NaiveTime::parse_from_str(time, "%H:%M")?),
             None => now,
@@ -62,11 +63,6 @@ impl Command for Insert {
             },
         };
 
-        let timestamp = match config.round_steps {
-            Some(ref round) => timestamp.duration_round(round.duration())?,
-            None => timestamp,
-        };
-
         let mut entries = db.list(timestamp.date())?;
         entries.sort_by_key(|e| e.timestamp);
 
@@ -76,18 +72,26 @@ impl Command for Insert {
             .cloned()
             .ok_or_else(|| anyhow::anyhow!("no entries after the given timestamp"))?;
 
-        let prev_timestamp = after_entry.timestamp;
-
-        db.update(Entry {
-            timestamp,
-            ..after_entry
-        })?;
-
-        db.add(NewEntry {
-            timestamp: prev_timestamp,
+        let entry = NewEntry {
+            timestamp: after_entry.timestamp,
             message: self.message.join(" "),
             long,
-        })
+        };
+
+        db.apply(
+            &format!("insert {}", entry.summary()),
+            vec![
+                Change::Update(Entry {
+                    timestamp,
+                    ..after_entry
+                }),
+                Change::Add(entry),
+            ],
+        )
+    }
+
+    fn writes(&self) -> bool {
+        true
     }
 }
 
@@ -0,0 +1,152 @@
+use super::Command;
+use crate::billing::format_amount;
+use crate::config::Config;
+use crate::db::NewInvoice;
+use crate::invoice::{self, Grouping};
+use crate::storage::Storage;
+use crate::format::format_duration;
+use crate::util::{DateRange, Parsable};
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::fs;
+use std::path::{Path, PathBuf};
+use yansi::Paint;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GroupBy {
+    /// One line item per day
+    Day,
+    /// One line item per task over the whole period
+    Task,
+}
+
+/// Bill the time tracked for a client and record the invoiced entries
+#[derive(Args)]
+pub struct Invoice {
+    /// Name of the client as configured in `clients`
+    #[arg(required_unless_present = "list")]
+    client: Option<String>,
+
+    /// Period to bill (e.g. `2025-07` or `last-month`) [default: last-month]
+    #[arg(short, long)]
+    date: Option<Parsable<DateRange>>,
+
+    /// How to combine the tracked intervals into line items
+    #[arg(short, long, value_enum, default_value_t = GroupBy::Day)]
+    group_by: GroupBy,
+
+    /// HTML file to write to [default: invoice-<number>.html]
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Render a PDF next to the HTML file as well
+    #[arg(long)]
+    pdf: bool,
+
+    /// Write the invoice as JSON next to the HTML file as well
+    #[arg(long)]
+    json: bool,
+
+    /// Render the invoice without numbering it or recording its entries as
+    /// invoiced
+    #[arg(long)]
+    draft: bool,
+
+    /// List all issued invoices
+    #[arg(short, long, conflicts_with_all = ["client", "draft"])]
+    list: bool,
+}
+
+impl Command for Invoice {
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()> {
+        if self.list {
+            let invoices = db.invoices()?;
+            if invoices.is_empty() {
+                println!("{}", "No invoices have been issued yet.".italic().dim());
+            }
+            for invoice in invoices {
+                println!(
+                    "{} {} {} {} {}",
+                    invoice.number.cyan(),
+                    invoice.client,
+                    DateRange::new(invoice.start, invoice.end)?.dim(),
+                    format_amount(invoice.total, &invoice.currency).bold(),
+                    format!("issued {}", invoice.issued_at.format("%Y-%m-%d")).dim(),
+                );
+            }
+            return Ok(());
+        }
+
+        let Some(ref name) = self.client else {
+            return Err(anyhow::anyhow!("no client given"));
+        };
+        let client = config.client(name)?;
+        let range = match self.date {
+            Some(Parsable(range)) => range,
+            None => "last-month".parse::<Parsable<DateRange>>()?.0,
+        };
+        let grouping = match self.group_by {
+            GroupBy::Day => Grouping::Day,
+            GroupBy::Task => Grouping::Task,
+        };
+
+        let entries = db.list_range(range.start, range.end)?;
+        let invoiced = db.invoiced_entries()?;
+        let mut invoice =
+            invoice::Invoice::new(client, range, &entries, &invoiced, grouping, config)?;
+
+        if self.draft {
+            return self.write(&invoice, "invoice-draft.html");
+        }
+
+        // The entries are only recorded as invoiced if the files have been
+        // written successfully.
+        let new = NewInvoice {
+            client: client.name.clone(),
+            start: range.start,
+            end: range.end,
+            total: invoice.total(),
+            currency: client.currency.clone(),
+            entry_ids: invoice.entry_ids.clone(),
+        };
+        db.record_invoice(new, &mut |record| {
+            invoice.number = Some(record.number.clone());
+            self.write(&invoice, &format!("invoice-{}.html", record.number))?;
+            println!(
+                "{} {} hours {}",
+                format!("Issued invoice {}:", record.number).green(),
+                format_duration(invoice.duration()),
+                invoice.format_amount(invoice.total()).bold(),
+            );
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    fn writes(&self) -> bool {
+        !self.list && !self.draft
+    }
+}
+
+impl Invoice {
+    fn write(&self, invoice: &invoice::Invoice, default_name: &str) -> Result<()> {
+        let html_path = self
+            .output
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(default_name));
+        write_file(&html_path, invoice.to_html().as_bytes())?;
+        if self.pdf {
+            write_file(&html_path.with_extension("pdf"), &invoice.to_pdf())?;
+        }
+        if self.json {
+            write_file(&html_path.with_extension("json"), invoice.to_json().as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+fn write_file(path: &Path, contents: &[u8]) -> Result<()> {
+    fs::write(path, contents)?;
+    println!("{} {}", "Written".green(), path.display().dim());
+    Ok(())
+}
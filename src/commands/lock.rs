@@ -0,0 +1,51 @@
+use super::Command;
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::util::{DateRange, Parsable};
+use anyhow::Result;
+use clap::Args;
+use yansi::Paint;
+
+/// Lock a period so that its entries can only be changed with `--force`
+#[derive(Args)]
+pub struct Lock {
+    /// Date range of the period to lock (e.g. `2025-07` or `last-month`)
+    #[arg(required_unless_present = "list")]
+    date: Option<Parsable<DateRange>>,
+
+    /// List all locked periods
+    #[arg(short, long, conflicts_with = "date")]
+    list: bool,
+}
+
+impl Command for Lock {
+    fn run(&self, db: &dyn Storage, _config: &Config) -> Result<()> {
+        if self.list {
+            let locks = db.locks()?;
+            if locks.is_empty() {
+                println!("{}", "No periods are locked.".italic().dim());
+            }
+            for lock in locks {
+                println!(
+                    "{} {}",
+                    DateRange::new(lock.start, lock.end)?.cyan(),
+                    format!("locked {}", lock.locked_at.format("%Y-%m-%d %H:%M")).dim(),
+                );
+            }
+            return Ok(());
+        }
+
+        let Some(Parsable(range)) = self.date else {
+            return Err(anyhow::anyhow!("no period given"));
+        };
+
+        db.lock(range.start, range.end)?;
+        println!("{}", format!("Locked {range}.").green());
+
+        Ok(())
+    }
+
+    fn writes(&self) -> bool {
+        !self.list
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config::Config;
 use crate::db::Database;
+use crate::storage::Storage;
 use anyhow::Result;
 
 macro_rules! re_export {
@@ -19,15 +20,52 @@ re_export! {
     delete
     edit
     insert
+    undo
+    redo
+    trash
+    history
+    seal
+    verify
+    lock
+    unlock
+    backup
+    restore
+    db
+    export
+    import
+    timesheet
+    invoice
+    report
 }
 
 pub trait Command {
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()>;
+
+    /// Whether the command may change the stored data. Expired entries are
+    /// only purged from the trash before such commands.
+    fn writes(&self) -> bool {
+        false
+    }
+}
+
+/// A command working on the database file itself, like backups, which can
+/// not be run on any other storage.
+pub trait DatabaseCommand {
     fn run(&self, db: &Database, config: &Config) -> Result<()>;
 }
 
+/// A registered command, see [`register_commands`](crate::register_commands).
+pub enum AnyCommand<'a> {
+    Storage(&'a dyn Command),
+    Database(&'a dyn DatabaseCommand),
+}
+
+/// Registers the given commands as subcommands. Commands marked with
+/// `[database]` implement [`DatabaseCommand`], all others [`Command`].
+#[doc(hidden)]
 #[macro_export]
 macro_rules! register_commands {
-    ( $( $command:tt )+ ) => {
+    ( $( $command:ident $( [$kind:ident] )? )+ ) => {
         #[derive(clap::Subcommand)]
         enum Commands {
             $(
@@ -35,16 +73,20 @@ macro_rules! register_commands {
             )*
         }
 
-        impl std::ops::Deref for Commands {
-            type Target = dyn $crate::commands::Command;
-
-            fn deref(&self) -> &Self::Target {
+        impl Commands {
+            fn command(&self) -> $crate::commands::AnyCommand<'_> {
                 match &self {
                     $(
-                        Self::$command(c) => c,
+                        Self::$command(c) => $crate::register_commands!(@any c $($kind)?),
                     )*
                 }
             }
         }
     };
+    (@any $c:ident) => {
+        $crate::commands::AnyCommand::Storage($c)
+    };
+    (@any $c:ident database) => {
+        $crate::commands::AnyCommand::Database($c)
+    };
 }
@@ -0,0 +1,34 @@
+use super::Command;
+use super::undo::print_operations;
+use crate::config::Config;
+use crate::storage::Storage;
+use anyhow::Result;
+use clap::Args;
+use yansi::Paint;
+
+/// Re-apply changes which have been reverted by undo
+#[derive(Args)]
+pub struct Redo {
+    /// Number of operations to redo
+    #[arg(default_value_t = 1)]
+    count: usize,
+}
+
+impl Command for Redo {
+    fn run(&self, db: &dyn Storage, _config: &Config) -> Result<()> {
+        let ops = db.redo(self.count)?;
+
+        if ops.is_empty() {
+            println!("{}", "There is nothing to redo.".italic().dim());
+            return Ok(());
+        }
+
+        print_operations("Redone", &ops);
+
+        Ok(())
+    }
+
+    fn writes(&self) -> bool {
+        true
+    }
+}
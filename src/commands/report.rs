@@ -0,0 +1,103 @@
+use super::Command;
+use crate::billing::{Billing, format_amount};
+use crate::config::Config;
+use crate::model::Entry;
+use crate::storage::Storage;
+use crate::summary::{DaySummary, Period, period_summaries};
+use crate::format::format_duration;
+use crate::util::{DateRange, Parsable};
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::collections::BTreeSet;
+use yansi::Paint;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum By {
+    Week,
+    Month,
+}
+
+/// Show work, billable hours, utilization and revenue per week or month
+#[derive(Args)]
+pub struct Report {
+    /// Date range to report on [default: this-month]
+    date: Option<Parsable<DateRange>>,
+
+    /// Period to sum up the tracked time by
+    #[arg(short, long, value_enum, default_value_t = By::Week)]
+    by: By,
+}
+
+impl Command for Report {
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()> {
+        let range = match self.date {
+            Some(Parsable(range)) => range,
+            None => "this-month".parse::<Parsable<DateRange>>()?.0,
+        };
+        let period = match self.by {
+            By::Week => Period::Week,
+            By::Month => Period::Month,
+        };
+
+        let entries = db.list_range(range.start, range.end)?;
+        if entries.is_empty() {
+            println!("{}", "There are no entries in this range.".italic().dim());
+            return Ok(());
+        }
+        let currency = currency(&entries, config)?;
+
+        println!(
+            "{}",
+            format!(
+                "{:<10} {:>8} {:>8} {:>9} {:>8} {:>14}",
+                "Period", "Work", "Pause", "Billable", "Util.", "Revenue"
+            )
+            .bold()
+        );
+        let mut total = DaySummary::default();
+        for (start, summary) in period_summaries(&entries, config, period)? {
+            println!(
+                "{}",
+                format_row(&period.label(start), &summary, &currency)
+            );
+            total += summary;
+        }
+        println!("{}", format_row("Total", &total, &currency).bold());
+
+        Ok(())
+    }
+}
+
+/// Returns the currency of the billed entries. Fails if they are billed in
+/// different currencies, as their revenue can not be summed up.
+fn currency(entries: &[Entry], config: &Config) -> Result<String> {
+    let mut currencies = BTreeSet::new();
+    for entry in entries {
+        if entry.message_matches(&config.break_regex)? {
+            continue;
+        }
+        let billing = Billing::of(entry, config)?;
+        if billing.billable && billing.rate.is_some() {
+            currencies.insert(billing.currency);
+        }
+    }
+    match currencies.len() {
+        0 => Ok(config.currency.clone()),
+        1 => Ok(currencies.pop_first().unwrap_or_default()),
+        _ => Err(anyhow::anyhow!(
+            "can not sum up revenue in different currencies ({})",
+            currencies.into_iter().collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+fn format_row(label: &str, summary: &DaySummary, currency: &str) -> String {
+    format!(
+        "{label:<10} {:>8} {:>8} {:>9} {:>7.0}% {:>14}",
+        format_duration(summary.work),
+        format_duration(summary.pause),
+        format_duration(summary.billable),
+        summary.utilization(),
+        format_amount(summary.revenue, currency),
+    )
+}
@@ -0,0 +1,70 @@
+use super::DatabaseCommand;
+use crate::config::Config;
+use crate::db::Database;
+use crate::db::BackupFile;
+use crate::util::ensure_interactive;
+use anyhow::Result;
+use clap::Args;
+use inquire::Select;
+use std::fmt;
+use std::path::PathBuf;
+use yansi::Paint;
+
+/// Restore the database from a backup
+#[derive(Args)]
+pub struct Restore {
+    /// Name of the backup (see `backup --list`) or path to a backup file
+    backup: Option<String>,
+}
+
+struct FormatableBackup(BackupFile);
+
+impl fmt::Display for FormatableBackup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.0.created_at.format("%Y-%m-%d %H:%M:%S"),
+            format!("({})", self.0.reason).dim()
+        )
+    }
+}
+
+impl DatabaseCommand for Restore {
+    fn run(&self, db: &Database, _config: &Config) -> Result<()> {
+        let path = match self.backup {
+            Some(ref name) => {
+                let in_dir = db.backup_dir().join(name);
+                match in_dir.is_file() {
+                    true => in_dir,
+                    false => PathBuf::from(name),
+                }
+            }
+            None => {
+                let backups = db.backups()?;
+                if backups.is_empty() {
+                    println!("{}", "No backups have been taken yet.".italic().dim());
+                    return Ok(());
+                }
+
+                ensure_interactive("pass the name of the backup to restore")?;
+
+                let options = backups.into_iter().map(FormatableBackup).collect();
+                Select::new("Select a backup to restore", options)
+                    .prompt()?
+                    .0
+                    .path
+            }
+        };
+
+        let previous = db.restore_backup(&path)?;
+        println!(
+            "{}\n{} {}",
+            format!("Restored backup {}.", path.display()).green(),
+            "The previous state has been saved as".dim(),
+            previous.name().dim()
+        );
+
+        Ok(())
+    }
+}
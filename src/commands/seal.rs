@@ -0,0 +1,56 @@
+use super::Command;
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::util::{DateRange, Parsable};
+use anyhow::Result;
+use clap::Args;
+use yansi::Paint;
+
+/// Close a period by storing a tamper-evident hash of its entries
+#[derive(Args)]
+pub struct Seal {
+    /// Date range of the period to seal (e.g. `2025-07` or `last-month`)
+    #[arg(required_unless_present = "list")]
+    date: Option<Parsable<DateRange>>,
+
+    /// List all sealed periods
+    #[arg(short, long, conflicts_with = "date")]
+    list: bool,
+}
+
+impl Command for Seal {
+    fn run(&self, db: &dyn Storage, _config: &Config) -> Result<()> {
+        if self.list {
+            let seals = db.seals()?;
+            if seals.is_empty() {
+                println!("{}", "No periods have been sealed yet.".italic().dim());
+            }
+            for seal in seals {
+                println!(
+                    "{} {} {}",
+                    DateRange::new(seal.start, seal.end)?.cyan(),
+                    format!("sealed {}", seal.sealed_at.format("%Y-%m-%d %H:%M")).dim(),
+                    seal.hash.dim()
+                );
+            }
+            return Ok(());
+        }
+
+        let Some(Parsable(range)) = self.date else {
+            return Err(anyhow::anyhow!("no period given"));
+        };
+
+        let seal = db.seal(range.start, range.end)?;
+        println!(
+            "{} {}",
+            format!("Sealed {range}:").green(),
+            seal.hash.dim()
+        );
+
+        Ok(())
+    }
+
+    fn writes(&self) -> bool {
+        !self.list
+    }
+}
@@ -0,0 +1,52 @@
+use super::Command;
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::timesheet;
+use crate::util::{DateRange, Parsable};
+use anyhow::Result;
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+use yansi::Paint;
+
+/// Render a printable timesheet of a month as HTML and optionally PDF
+#[derive(Args)]
+pub struct Timesheet {
+    /// Month or period of at most 31 days (e.g. `2025-07` or `last-month`)
+    /// [default: this-month]
+    date: Option<Parsable<DateRange>>,
+
+    /// HTML file to write to [default: timesheet-<start date>.html]
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Render a PDF next to the HTML file as well
+    #[arg(long)]
+    pdf: bool,
+}
+
+impl Command for Timesheet {
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()> {
+        let range = match self.date {
+            Some(Parsable(range)) => range,
+            None => "this-month".parse::<Parsable<DateRange>>()?.0,
+        };
+        let entries = db.list_range(range.start, range.end)?;
+        let sheet = timesheet::Timesheet::new(range, &entries, config)?;
+
+        let html_path = self
+            .output
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(format!("timesheet-{}.html", range.start)));
+        fs::write(&html_path, sheet.to_html())?;
+        println!("{} {}", "Written".green(), html_path.display().dim());
+
+        if self.pdf {
+            let pdf_path = html_path.with_extension("pdf");
+            fs::write(&pdf_path, sheet.to_pdf())?;
+            println!("{} {}", "Written".green(), pdf_path.display().dim());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+use super::Command;
+use crate::clock;
+use crate::config::Config;
+use crate::model::DeletedEntry;
+use crate::storage::Storage;
+use crate::util::{FormatableEntry, ensure_interactive};
+use anyhow::Result;
+use chrono::Duration;
+use clap::{Args, Subcommand};
+use fancy_duration::FancyDuration;
+use inquire::MultiSelect;
+use std::fmt;
+use yansi::Paint;
+
+/// List, restore and purge deleted entries
+#[derive(Args)]
+#[command(visible_aliases = ["t"])]
+pub struct Trash {
+    #[command(subcommand)]
+    action: Option<TrashAction>,
+}
+
+#[derive(Subcommand)]
+enum TrashAction {
+    /// List all deleted entries
+    List,
+
+    /// Restore deleted entries
+    Restore {
+        /// IDs of the entries to restore
+        ids: Vec<u32>,
+    },
+
+    /// Permanently remove deleted entries
+    Purge {
+        /// Only purge entries deleted longer ago than the given duration
+        /// (defaults to `trash_retention` from the config)
+        #[arg(short, long)]
+        older_than: Option<FancyDuration<Duration>>,
+
+        /// Purge all deleted entries
+        #[arg(short, long, conflicts_with = "older_than")]
+        all: bool,
+    },
+}
+
+impl Command for Trash {
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()> {
+        match self.action {
+            None | Some(TrashAction::List) => list(db, config),
+            Some(TrashAction::Restore { ref ids }) => restore(db, config, ids),
+            Some(TrashAction::Purge {
+                ref older_than,
+                all,
+            }) => purge(db, config, older_than.as_ref(), all),
+        }
+    }
+
+    fn writes(&self) -> bool {
+        matches!(
+            self.action,
+            Some(TrashAction::Restore { .. } | TrashAction::Purge { .. })
+        )
+    }
+}
+
+struct FormatableDeletedEntry<'c, 'e> {
+    config: &'c Config,
+    deleted: &'e DeletedEntry,
+}
+
+impl fmt::Display for FormatableDeletedEntry<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            format!("#{}", self.deleted.entry.id).dim(),
+            FormatableEntry::new(&self.deleted.entry, self.config, false).with_date(true),
+            format!(
+                "(deleted {})",
+                self.deleted.deleted_at.format("%Y-%m-%d %H:%M")
+            )
+            .dim()
+        )
+    }
+}
+
+fn list(db: &dyn Storage, config: &Config) -> Result<()> {
+    let deleted = db.list_deleted()?;
+
+    if deleted.is_empty() {
+        println!("{}", "The trash is empty.".italic().dim());
+        return Ok(());
+    }
+
+    for deleted in &deleted {
+        println!("{}", FormatableDeletedEntry { config, deleted });
+    }
+
+    Ok(())
+}
+
+fn restore(db: &dyn Storage, config: &Config, ids: &[u32]) -> Result<()> {
+    let ids = match ids.is_empty() {
+        false => ids.to_vec(),
+        true => {
+            let deleted = db.list_deleted()?;
+            if deleted.is_empty() {
+                println!("{}", "The trash is empty.".italic().dim());
+                return Ok(());
+            }
+
+            ensure_interactive("pass the IDs of the entries to restore")?;
+
+            let options: Vec<_> = deleted
+                .iter()
+                .map(|deleted| FormatableDeletedEntry { config, deleted })
+                .collect();
+            MultiSelect::new("Select entries to restore", options)
+                .prompt()?
+                .into_iter()
+                .map(|e| e.deleted.entry.id)
+                .collect()
+        }
+    };
+
+    db.restore_deleted(&ids)
+}
+
+fn purge(
+    db: &dyn Storage,
+    config: &Config,
+    older_than: Option<&FancyDuration<Duration>>,
+    all: bool,
+) -> Result<()> {
+    let now = clock::now();
+
+    let before = match (all, older_than.or(config.trash_retention.as_ref())) {
+        (true, _) => now,
+        (false, Some(retention)) => now - retention.duration(),
+        (false, None) => {
+            return Err(anyhow::anyhow!(
+                "pass --older-than or --all, or set trash_retention in the config"
+            ));
+        }
+    };
+
+    let count = db.purge_deleted_before(before)?;
+    println!(
+        "{}",
+        format!("Permanently removed {count} entries from the trash.").dim()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use super::Command;
+use crate::config::Config;
+use crate::db::Operation;
+use crate::storage::Storage;
+use anyhow::Result;
+use clap::Args;
+use yansi::Paint;
+
+/// Revert the last changes made by add, edit, delete, insert or restoring from the trash
+#[derive(Args)]
+#[command(visible_aliases = ["u"])]
+pub struct Undo {
+    /// Number of operations to undo
+    #[arg(default_value_t = 1)]
+    count: usize,
+}
+
+impl Command for Undo {
+    fn run(&self, db: &dyn Storage, _config: &Config) -> Result<()> {
+        let ops = db.undo(self.count)?;
+
+        if ops.is_empty() {
+            println!("{}", "There is nothing to undo.".italic().dim());
+            return Ok(());
+        }
+
+        print_operations("Undone", &ops);
+
+        Ok(())
+    }
+
+    fn writes(&self) -> bool {
+        true
+    }
+}
+
+pub(super) fn print_operations(label: &str, ops: &[Operation]) {
+    for op in ops {
+        println!(
+            "{} {} {}",
+            format!("{label}:").bold(),
+            op.description,
+            format!("({})", op.created_at.format("%Y-%m-%d %H:%M")).dim()
+        );
+    }
+}
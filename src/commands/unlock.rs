@@ -0,0 +1,31 @@
+use super::Command;
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::util::{DateRange, Parsable};
+use anyhow::Result;
+use clap::Args;
+use yansi::Paint;
+
+/// Unlock a previously locked period
+#[derive(Args)]
+pub struct Unlock {
+    /// Date range of the period to unlock
+    date: Parsable<DateRange>,
+}
+
+impl Command for Unlock {
+    fn run(&self, db: &dyn Storage, _config: &Config) -> Result<()> {
+        let Parsable(range) = self.date;
+
+        match db.unlock(range.start, range.end)? {
+            0 => println!("{}", format!("No locked periods overlap {range}.").italic().dim()),
+            _ => println!("{}", format!("Unlocked {range}.").green()),
+        }
+
+        Ok(())
+    }
+
+    fn writes(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,57 @@
+use super::Command;
+use crate::config::Config;
+use crate::db::SealViolation;
+use crate::storage::Storage;
+use crate::util::{DateRange, FormatableEntry};
+use anyhow::Result;
+use clap::Args;
+use yansi::Paint;
+
+/// Verify that sealed periods have not been changed after sealing
+#[derive(Args)]
+pub struct Verify {}
+
+impl Command for Verify {
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()> {
+        let violations = db.verify_seals()?;
+
+        if violations.is_empty() {
+            let count = db.seals()?.len();
+            println!("{}", format!("All {count} sealed periods are intact.").green());
+            return Ok(());
+        }
+
+        for violation in &violations {
+            match violation {
+                SealViolation::BrokenChain { seal } => println!(
+                    "{} {}",
+                    DateRange::new(seal.start, seal.end)?.cyan(),
+                    "seal has been altered or the chain is broken".red()
+                ),
+                SealViolation::Changed { seal, entry } => println!(
+                    "{} {} {}",
+                    DateRange::new(seal.start, seal.end)?.cyan(),
+                    "changed:".red(),
+                    FormatableEntry::new(entry, config, false).with_date(true)
+                ),
+                SealViolation::Removed { seal, entry_id } => println!(
+                    "{} {} {}",
+                    DateRange::new(seal.start, seal.end)?.cyan(),
+                    "removed:".red(),
+                    format!("#{entry_id}").dim()
+                ),
+                SealViolation::Added { seal, entry } => println!(
+                    "{} {} {}",
+                    DateRange::new(seal.start, seal.end)?.cyan(),
+                    "added:".red(),
+                    FormatableEntry::new(entry, config, false).with_date(true)
+                ),
+            }
+        }
+
+        Err(anyhow::anyhow!(
+            "found {} violations of sealed periods",
+            violations.len()
+        ))
+    }
+}
@@ -1,10 +1,12 @@
 use super::Command;
+use crate::clock;
 use crate::config::Config;
-use crate::db::Database;
 use crate::model::Entry;
-use crate::util::{Parsable, select_date};
+use crate::storage::Storage;
+use crate::summary::{DaySummary, durations};
+use crate::util::{DateRange, Parsable, select_date};
 use anyhow::Result;
-use chrono::{Duration, Local, NaiveDate, TimeDelta};
+use chrono::{NaiveDate, TimeDelta};
 use clap::Args;
 use crossterm::event::{Event, KeyCode};
 use crossterm::{cursor, event, execute, terminal};
@@ -23,8 +25,8 @@ macro_rules! println_cr {
 #[derive(Args)]
 #[command(visible_aliases = ["v"])]
 pub struct View {
-    /// Date of the list
-    date: Option<Parsable<NaiveDate>>,
+    /// Date or date range of the list
+    date: Option<Parsable<DateRange>>,
 
     /// Select date from an interactive calender
     #[arg(short, long)]
@@ -38,25 +40,30 @@ pub struct View {
     #[arg(long)]
     csv: bool,
 
-    /// Interactively page through days
+    /// Display the IDs of the entries
+    #[arg(short, long)]
+    ids: bool,
+
+    /// Interactively page through days, within the range if one is given
     #[arg(short, long)]
     paging: bool,
 }
 
 impl Command for View {
-    fn run(&self, db: &Database, config: &Config) -> Result<()> {
-        let date = match self.date {
-            Some(Parsable(date_str)) => date_str,
-            None if self.select => select_date()?,
-            _ => Local::now().date_naive(),
+    fn run(&self, db: &dyn Storage, config: &Config) -> Result<()> {
+        let range = match self.date {
+            Some(Parsable(range)) => range,
+            None if self.select => DateRange::day(select_date()?),
+            _ => DateRange::day(clock::today()),
         };
 
         if self.paging {
-            return paging_view(db, config, date, self.long);
+            // A single day is only the day to start paging at.
+            let bounds = (!range.is_single_day()).then_some(range);
+            return paging_view(db, config, range.start, bounds, self.long, self.ids);
         }
 
-        let mut entries = db.list(date)?;
-        entries.sort_by_key(|e| e.timestamp);
+        let entries = db.list_range(range.start, range.end)?;
 
         if self.csv {
             for e in entries {
@@ -65,35 +72,57 @@ impl Command for View {
             return Ok(());
         }
 
-        print_entries(config, &entries, self.long)
+        if range.is_single_day() {
+            print_entries(config, &entries, self.long, self.ids)?;
+            return Ok(());
+        }
+
+        print_range(config, &entries, self.long, self.ids)
     }
 }
 
-fn print_entries(config: &Config, entries: &[Entry], long: bool) -> Result<()> {
+fn print_range(config: &Config, entries: &[Entry], long: bool, ids: bool) -> Result<()> {
     if entries.is_empty() {
-        println_cr!("{}", "There are no entries for this day.".italic().dim());
+        println_cr!("{}", "There are no entries in this range.".italic().dim());
         return Ok(());
     }
 
-    let mut sum = Duration::zero();
-    let mut pause_time = Duration::zero();
-    let mut last_timestamp = None;
-
-    for (i, e) in entries.iter().enumerate() {
-        let duration = match last_timestamp {
-            Some(last_timestamp) => {
-                let duration = e.timestamp - last_timestamp;
-                match e.message_matches(&config.break_regex)? {
-                    true => pause_time += duration,
-                    false => sum += duration,
-                }
-                Some(duration)
-            }
-            None => None,
-        };
+    let mut total = DaySummary::default();
+
+    for day in entries.chunk_by(|a, b| a.timestamp.date() == b.timestamp.date()) {
+        println_cr!(
+            "{}",
+            day[0].timestamp.format("%A, %-d %B, %C%y").bold().underline()
+        );
+        total += print_entries(config, day, long, ids)?;
+        println_cr!("");
+    }
 
-        last_timestamp = Some(e.timestamp);
+    println_cr!(
+        "Total: {} ({}{})",
+        total.work.fancy_duration().truncate(2).to_string().cyan().bold(),
+        format!("{} pause", total.pause.fancy_duration().truncate(2)).green(),
+        format_billable(config, &total)
+    );
 
+    Ok(())
+}
+
+/// Prints the given entries of a single day and returns their summary.
+fn print_entries(
+    config: &Config,
+    entries: &[Entry],
+    long: bool,
+    ids: bool,
+) -> Result<DaySummary> {
+    if entries.is_empty() {
+        println_cr!("{}", "There are no entries for this day.".italic().dim());
+        return Ok(DaySummary::default());
+    }
+
+    let summary = DaySummary::compute(entries, config)?;
+
+    for (i, (e, duration)) in entries.iter().zip(durations(entries)).enumerate() {
         print!(
             "{}{}{} ",
             "[".dim(),
@@ -101,19 +130,45 @@ fn print_entries(config: &Config, entries: &[Entry], long: bool) -> Result<()> {
             "]".dim(),
         );
 
+        if ids {
+            print!("{} ", format!("#{}", e.id).dim());
+        }
+
         println_cr!("{}", e.formatted(config, long, duration)?);
     }
 
     println_cr!(
-        "\n     {} ({})",
-        sum.fancy_duration().truncate(2).to_string().cyan().bold(),
-        format!("{} pause", pause_time.fancy_duration().truncate(2)).green()
+        "\n     {} ({}{})",
+        summary.work.fancy_duration().truncate(2).to_string().cyan().bold(),
+        format!("{} pause", summary.pause.fancy_duration().truncate(2)).green(),
+        format_billable(config, &summary)
     );
 
-    Ok(())
+    Ok(summary)
+}
+
+/// Formats the billable time of a summary as third part next to work and
+/// pause, if billable time is configured at all.
+fn format_billable(config: &Config, summary: &DaySummary) -> String {
+    if !config.tracks_billing() {
+        return String::new();
+    }
+    let billable = format!(
+        "{} billable, {:.0}%",
+        summary.billable.fancy_duration().truncate(2),
+        summary.utilization()
+    );
+    format!(", {}", billable.yellow())
 }
 
-fn paging_view(db: &Database, config: &Config, start_date: NaiveDate, long: bool) -> Result<()> {
+fn paging_view(
+    db: &dyn Storage,
+    config: &Config,
+    start_date: NaiveDate,
+    bounds: Option<DateRange>,
+    long: bool,
+    ids: bool,
+) -> Result<()> {
     terminal::enable_raw_mode()?;
     defer! {
         terminal::disable_raw_mode().ok();
@@ -158,16 +213,20 @@ fn paging_view(db: &Database, config: &Config, start_date: NaiveDate, long: bool
                 .on_bright_black()
         );
 
-        print_entries(config, &entries, long)?;
+        print_entries(config, &entries, long, ids)?;
 
         if let Event::Key(event) = event::read()? {
-            match event.code {
-                KeyCode::Right | KeyCode::Char('l') => date += TimeDelta::days(1),
-                KeyCode::Left | KeyCode::Char('h') => date -= TimeDelta::days(1),
-                KeyCode::Up | KeyCode::Char('k') => date += TimeDelta::days(7),
-                KeyCode::Down | KeyCode::Char('j') => date -= TimeDelta::days(7),
+            let days = match event.code {
+                KeyCode::Right | KeyCode::Char('l') => 1,
+                KeyCode::Left | KeyCode::Char('h') => -1,
+                KeyCode::Up | KeyCode::Char('k') => 7,
+                KeyCode::Down | KeyCode::Char('j') => -7,
                 KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('c') => break,
-                _ => {}
+                _ => 0,
+            };
+            date += TimeDelta::days(days);
+            if let Some(bounds) = bounds {
+                date = date.clamp(bounds.start, bounds.end);
             }
         }
     }
@@ -1,3 +1,4 @@
+use crate::rounding::Rounding;
 use anyhow::Result;
 use fancy_duration::FancyDuration;
 use figment::Figment;
@@ -30,6 +31,22 @@ fn default_end_regex() -> String {
     "(?i)^end$".to_string()
 }
 
+fn default_ledger_account() -> String {
+    "Work".to_string()
+}
+
+fn default_currency() -> String {
+    "EUR".to_string()
+}
+
+fn default_backup_retention() -> Option<FancyDuration<chrono::Duration>> {
+    Some(FancyDuration(chrono::Duration::days(30)))
+}
+
+fn default_true() -> bool {
+    true
+}
+
 pub fn default_storage_dir() -> PathBuf {
     dirs::data_dir()
         .expect("data directory")
@@ -51,10 +68,122 @@ pub struct Config {
     #[serde(default = "default_end_regex")]
     pub end_regex: String,
 
+    /// Deprecated in favor of `rounding`, which it falls back to as rounding
+    /// to the nearest step.
     pub round_steps: Option<FancyDuration<chrono::Duration>>,
+
+    pub rounding: Option<Rounding>,
+
+    pub trash_retention: Option<FancyDuration<chrono::Duration>>,
+
+    #[serde(default = "default_backup_retention")]
+    pub backup_retention: Option<FancyDuration<chrono::Duration>>,
+
+    #[serde(default)]
+    pub daily_backup: bool,
+
+    #[serde(default = "default_ledger_account")]
+    pub ledger_default_account: String,
+
+    #[serde(default)]
+    pub ledger_accounts: Vec<LedgerAccount>,
+
+    pub employee: Option<String>,
+
+    pub employer: Option<String>,
+
+    #[serde(default)]
+    pub clients: Vec<Client>,
+
+    pub invoice_issuer: Option<String>,
+
+    #[serde(default)]
+    pub projects: Vec<Project>,
+
+    #[serde(default)]
+    pub default_billable: bool,
+
+    #[serde(default = "default_currency")]
+    pub currency: String,
+}
+
+/// Selects entries by a regular expression on their message and by one of
+/// their tags, which are the words of the message starting with `#`. A
+/// filter without both selects all entries.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct EntryFilter {
+    pub message: Option<String>,
+    pub tag: Option<String>,
+}
+
+/// Maps the entries selected by the filter to an account in the ledger
+/// export.
+#[derive(Clone, Debug, Deserialize)]
+pub struct LedgerAccount {
+    #[serde(flatten)]
+    pub filter: EntryFilter,
+    pub account: String,
+}
+
+/// A client whose entries, selected by the filter, are billed by the hour.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Client {
+    pub name: String,
+    #[serde(flatten)]
+    pub filter: EntryFilter,
+    pub rate: f64,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// Billing increment each interval is rounded up to, as a shorthand for
+    /// `rounding`.
+    pub increment: Option<FancyDuration<chrono::Duration>>,
+    pub rounding: Option<Rounding>,
+    pub address: Option<String>,
+}
+
+/// A project whose entries, selected by the filter, are billed at its rate
+/// or not at all.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Project {
+    pub name: String,
+    #[serde(flatten)]
+    pub filter: EntryFilter,
+    pub rate: Option<f64>,
+    #[serde(default = "default_true")]
+    pub billable: bool,
 }
 
 impl Config {
+    /// Returns the client with the given name, ignoring case.
+    pub fn client(&self, name: &str) -> Result<&Client> {
+        self.clients
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| anyhow::anyhow!("no client named {name} in the config"))
+    }
+
+    /// Returns the policy for rounding the billed time of the client, falling
+    /// back to the global one.
+    pub fn rounding_for(&self, client: Option<&Client>) -> Option<Rounding> {
+        let client_rounding = client.and_then(|c| {
+            c.rounding
+                .clone()
+                .or_else(|| c.increment.as_ref().map(|i| Rounding::up(i.duration())))
+        });
+        client_rounding
+            .or_else(|| self.rounding.clone())
+            .or_else(|| {
+                self.round_steps
+                    .as_ref()
+                    .map(|s| Rounding::nearest(s.duration()))
+            })
+    }
+
+    /// Returns whether any rules for billable time are configured.
+    pub fn tracks_billing(&self) -> bool {
+        !self.projects.is_empty() || !self.clients.is_empty() || self.default_billable
+    }
+
     pub fn parse<T: AsRef<Path>>(dir: Option<T>) -> Result<Self> {
         dir.map(Self::parse_from_file)
             .unwrap_or_else(Self::parse_from_cfgdir)
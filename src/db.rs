@@ -1,13 +1,39 @@
-use crate::model::{Entry, NewEntry};
+use crate::clock;
+use crate::model::{Change, Entry, NewEntry};
 use anyhow::Result;
-use chrono::NaiveDate;
+use chrono::{Duration, NaiveDate};
 use include_dir::{Dir, include_dir};
-use rusqlite::{Connection, params};
-use rusqlite_migration::Migrations;
+use rusqlite::{Connection, OptionalExtension, Row, Transaction, TransactionBehavior, params};
+use rusqlite_migration::{Migrations, SchemaVersion};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
+mod backup;
+mod history;
+mod invoice;
+mod lock;
+mod maintenance;
+mod operation;
+mod seal;
+mod trash;
+
+pub use backup::BackupFile;
+pub use history::{HistoryAction, HistoryEntry};
+pub(crate) use invoice::next_invoice_number;
+pub use invoice::{InvoiceRecord, NewInvoice};
+pub use lock::Lock;
+pub use maintenance::{EntryProblem, Stats};
+pub use operation::{EntryChange, Operation};
+pub use seal::{SealViolation, SealedPeriod};
+pub(crate) use trash::restore_description;
+
+const DB_FILE: &str = "db.sqlite";
+
+/// How long to wait for locks held by other processes, e.g. a status bar
+/// polling the database, before failing with `SQLITE_BUSY`.
+const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
 static MIGRATIONS_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/migrations");
 
 static MIGRATIONS: LazyLock<Migrations<'static>> =
@@ -15,69 +41,249 @@ static MIGRATIONS: LazyLock<Migrations<'static>> =
 
 pub struct Database {
     conn: Connection,
+    base_dir: PathBuf,
+    force: bool,
+    backup_retention: Option<Duration>,
 }
 
 impl Database {
     pub fn new<P: AsRef<Path>>(base_dir: P) -> Result<Self> {
         let base_dir = base_dir.as_ref();
         fs::create_dir_all(base_dir)?;
-        let mut conn = Connection::open(base_dir.join("db.sqlite"))?;
-        MIGRATIONS.to_latest(&mut conn)?;
-        Ok(Self { conn })
+        let conn = open(&base_dir.join(DB_FILE))?;
+        let mut db = Self {
+            conn,
+            base_dir: base_dir.to_path_buf(),
+            force: false,
+            backup_retention: None,
+        };
+
+        let existing = matches!(
+            MIGRATIONS.current_version(&db.conn)?,
+            SchemaVersion::Inside(_)
+        );
+        if existing && MIGRATIONS.pending_migrations(&db.conn)? > 0 {
+            db.backup("migration")?;
+        }
+        MIGRATIONS.to_latest(&mut db.conn)?;
+
+        Ok(db)
     }
 
-    pub fn add(&self, entry: NewEntry) -> Result<()> {
-        self.conn.execute(
-            "INSERT INTO entry (date, time, message, long)
-            VALUES (?, ?, ?, ?)",
-            params![
-                entry.timestamp.date(),
-                entry.timestamp.time(),
-                entry.message,
-                entry.long
-            ],
+    pub fn add(&self, entry: NewEntry) -> Result<u32> {
+        self.transaction(|db| {
+            let forced = db.check_locked(&[entry.timestamp.date()])?;
+            let now = clock::now();
+            db.conn.execute(
+                "INSERT INTO entry (date, time, message, long, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?)",
+                params![
+                    entry.timestamp.date(),
+                    entry.timestamp.time(),
+                    entry.message,
+                    entry.long,
+                    now,
+                    now
+                ],
+            )?;
+            let id = db.conn.last_insert_rowid() as u32;
+            db.record_history(id, HistoryAction::Create, now, forced)?;
+            Ok(id)
+        })
+    }
+
+    pub fn get(&self, id: u32) -> Result<Option<Entry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, datetime(date || ' ' || time) as timestamp, message, long
+            FROM entry WHERE id = ? AND deleted_at IS NULL",
         )?;
-        Ok(())
+        Ok(stmt.query_row(params![id], entry_from_row).optional()?)
     }
 
     pub fn list(&self, date: NaiveDate) -> Result<Vec<Entry>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, datetime(date || ' ' || time) as timestamp, message, long
-            FROM entry WHERE date = ?",
+            FROM entry WHERE date = ? AND deleted_at IS NULL",
         )?;
-        let rows = stmt.query_map(params![date], |row| {
-            Ok(Entry {
-                id: row.get(0)?,
-                timestamp: row.get(1)?,
-                message: row.get(2)?,
-                long: row.get(3)?,
-            })
-        })?;
+        let rows = stmt.query_map(params![date], entry_from_row)?;
         Ok(rows.collect::<Result<Vec<_>, _>>()?)
     }
 
-    pub fn update(&self, entry: Entry) -> Result<()> {
-        self.conn.execute(
-            "UPDATE entry
-            SET date = ?, time = ?, message = ?, long = ?
-            WHERE id = ?",
-            params![
-                entry.timestamp.date(),
-                entry.timestamp.time(),
-                entry.message,
-                entry.long,
-                entry.id
-            ],
+    pub fn list_range(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Entry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, datetime(date || ' ' || time) as timestamp, message, long
+            FROM entry WHERE date BETWEEN ? AND ? AND deleted_at IS NULL
+            ORDER BY date, time",
         )?;
-        Ok(())
+        let rows = stmt.query_map(params![from, to], entry_from_row)?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
     }
 
-    pub fn delete(&self, id: u32) -> Result<()> {
-        self.conn.execute(
-            "DELETE FROM entry
-            WHERE id = ?",
-            params![id],
+    /// Returns all entries ordered by their timestamp.
+    pub fn list_all(&self) -> Result<Vec<Entry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, datetime(date || ' ' || time) as timestamp, message, long
+            FROM entry WHERE deleted_at IS NULL
+            ORDER BY date, time",
         )?;
-        Ok(())
+        let rows = stmt.query_map([], entry_from_row)?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    pub fn update(&self, entry: Entry) -> Result<()> {
+        self.transaction(|db| {
+            let mut dates = vec![entry.timestamp.date()];
+            dates.extend(db.entry_date(entry.id)?);
+            let forced = db.check_locked(&dates)?;
+            let now = clock::now();
+            let updated = db.conn.execute(
+                "UPDATE entry
+                SET date = ?, time = ?, message = ?, long = ?, updated_at = ?
+                WHERE id = ? AND deleted_at IS NULL",
+                params![
+                    entry.timestamp.date(),
+                    entry.timestamp.time(),
+                    entry.message,
+                    entry.long,
+                    now,
+                    entry.id
+                ],
+            )?;
+            if updated != 1 {
+                return Err(anyhow::anyhow!("no entry with id {}", entry.id));
+            }
+            db.record_history(entry.id, HistoryAction::Update, now, forced)
+        })
+    }
+
+    /// Moves the entry with the given ID to the trash.
+    pub fn delete(&self, id: u32) -> Result<()> {
+        self.transaction(|db| {
+            let forced = db.check_locked(db.entry_date(id)?.as_slice())?;
+            let now = clock::now();
+            let deleted = db.conn.execute(
+                "UPDATE entry
+                SET deleted_at = ?
+                WHERE id = ? AND deleted_at IS NULL",
+                params![now, id],
+            )?;
+            if deleted != 1 {
+                return Err(anyhow::anyhow!("no entry with id {id}"));
+            }
+            db.record_history(id, HistoryAction::Delete, now, forced)
+        })
+    }
+
+    /// Applies all given changes in a single transaction and records
+    /// them as one operation with the given description, which can be
+    /// undone afterwards. A backup is taken before if entries are deleted.
+    pub fn apply(&self, description: &str, changes: Vec<Change>) -> Result<()> {
+        if changes.iter().any(|c| matches!(c, Change::Delete(_))) {
+            self.backup_outside_transaction("delete")?;
+        }
+        self.transaction(|db| {
+            let operation_id = db.begin_operation(description)?;
+
+            for change in changes {
+                let (old, new) = match change {
+                    Change::Add(entry) => {
+                        let id = db.add(entry)?;
+                        (None, db.get(id)?)
+                    }
+                    Change::Update(entry) => {
+                        let old = db.get_existing(entry.id)?;
+                        db.update(entry.clone())?;
+                        (Some(old), Some(entry))
+                    }
+                    Change::Delete(id) => {
+                        let old = db.get_existing(id)?;
+                        db.delete(id)?;
+                        (Some(old), None)
+                    }
+                };
+                db.record_change(operation_id, old.as_ref(), new.as_ref())?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Runs the given closure in a transaction, which is committed if the
+    /// closure succeeds and rolled back otherwise. When called within
+    /// another transaction, the closure becomes part of the outer one.
+    ///
+    /// The transaction acquires the write lock right away, so that
+    /// concurrent writers wait for each other instead of failing midway.
+    pub fn transaction<T>(&self, f: impl FnOnce(&Self) -> Result<T>) -> Result<T> {
+        if !self.conn.is_autocommit() {
+            return f(self);
+        }
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let res = f(self)?;
+        tx.commit()?;
+        Ok(res)
+    }
+
+    fn get_existing(&self, id: u32) -> Result<Entry> {
+        self.get(id)?
+            .ok_or_else(|| anyhow::anyhow!("no entry with id {id}"))
+    }
+}
+
+/// Opens the database file in WAL mode, which allows reading while another
+/// process writes.
+fn open(path: &Path) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    Ok(conn)
+}
+
+fn entry_from_row(row: &Row) -> rusqlite::Result<Entry> {
+    Ok(Entry {
+        id: row.get(0)?,
+        timestamp: row.get(1)?,
+        message: row.get(2)?,
+        long: row.get(3)?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::Database;
+    use crate::model::{Entry, NewEntry};
+    use chrono::NaiveDateTime;
+
+    #[test]
+    fn update_and_delete_require_existing_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = Database::new(tmp.path()).unwrap();
+        let timestamp =
+            NaiveDateTime::parse_from_str("2025-07-24 09:00", "%Y-%m-%d %H:%M").unwrap();
+        let id = db
+            .add(NewEntry {
+                timestamp,
+                message: "start".to_string(),
+                long: None,
+            })
+            .unwrap();
+        db.delete(id).unwrap();
+
+        for id in [id, 42] {
+            let entry = Entry {
+                id,
+                timestamp,
+                message: "end".to_string(),
+                long: None,
+            };
+            let err = db.update(entry).unwrap_err();
+            assert_eq!(format!("no entry with id {id}"), err.to_string());
+            let err = db.delete(id).unwrap_err();
+            assert_eq!(format!("no entry with id {id}"), err.to_string());
+        }
+        assert!(db.history(42).unwrap().is_empty());
+        assert_eq!(2, db.history(id).unwrap().len());
+        assert_eq!("start", db.list_deleted().unwrap()[0].entry.message);
     }
 }
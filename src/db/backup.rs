@@ -0,0 +1,176 @@
+use super::{Database, MIGRATIONS};
+use anyhow::Result;
+use chrono::{Duration, Local, NaiveDateTime};
+use rusqlite::MAIN_DB;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BACKUP_DIR: &str = "backups";
+const BACKUP_EXT: &str = "sqlite";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S%.3f";
+
+/// A snapshot of the database in the backup directory.
+#[derive(Clone, Debug)]
+pub struct BackupFile {
+    pub path: PathBuf,
+    pub created_at: NaiveDateTime,
+    /// Why the backup has been taken, e.g. `manual` or `migration`.
+    pub reason: String,
+    pub size: u64,
+}
+
+impl BackupFile {
+    /// Returns the file name of the backup, which identifies it.
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn from_path(path: PathBuf) -> Option<Self> {
+        if path.extension().is_none_or(|ext| ext != BACKUP_EXT) {
+            return None;
+        }
+        let stem = path.file_stem()?.to_str()?;
+        // The timestamp itself contains a single dash.
+        let (date, rest) = stem.split_once('-')?;
+        let (time, reason) = rest.split_once('-')?;
+        let created_at =
+            NaiveDateTime::parse_from_str(&format!("{date}-{time}"), TIMESTAMP_FORMAT).ok()?;
+        let size = fs::metadata(&path).ok()?.len();
+        Some(Self {
+            reason: reason.to_string(),
+            path,
+            created_at,
+            size,
+        })
+    }
+}
+
+impl Database {
+    /// Removes backups older than the given duration whenever a new backup
+    /// is taken.
+    pub fn with_backup_retention(mut self, retention: Option<Duration>) -> Self {
+        self.backup_retention = retention;
+        self
+    }
+
+    /// Returns the directory the backups are stored in.
+    pub fn backup_dir(&self) -> PathBuf {
+        self.base_dir.join(BACKUP_DIR)
+    }
+
+    /// Takes a snapshot of the database using SQLite's online backup API.
+    pub fn backup(&self, reason: &str) -> Result<BackupFile> {
+        let dir = self.backup_dir();
+        fs::create_dir_all(&dir)?;
+
+        // Backups are files on disk, so they are named after the real time
+        // rather than the tracker's clock, which may be fixed.
+        let now = Local::now().naive_local();
+        let path = dir.join(format!(
+            "{}-{reason}.{BACKUP_EXT}",
+            now.format(TIMESTAMP_FORMAT)
+        ));
+        self.conn.backup(MAIN_DB, &path, None)?;
+
+        if let Some(retention) = self.backup_retention {
+            self.prune_backups(now - retention)?;
+        }
+
+        BackupFile::from_path(path)
+            .ok_or_else(|| anyhow::anyhow!("failed to read the created backup"))
+    }
+
+    /// Takes a backup before changes which may be hard to revert, unless the
+    /// changes are part of a larger transaction, whose caller takes care of
+    /// it.
+    pub(super) fn backup_outside_transaction(&self, reason: &str) -> Result<()> {
+        if self.conn.is_autocommit() {
+            self.backup(reason)?;
+        }
+        Ok(())
+    }
+
+    /// Takes a daily backup, unless one has already been taken today.
+    pub fn backup_daily(&self) -> Result<Option<BackupFile>> {
+        let today = Local::now().date_naive();
+        let exists = self
+            .backups()?
+            .iter()
+            .any(|b| b.reason == "daily" && b.created_at.date() == today);
+        match exists {
+            true => Ok(None),
+            false => self.backup("daily").map(Some),
+        }
+    }
+
+    /// Returns all backups, latest first.
+    pub fn backups(&self) -> Result<Vec<BackupFile>> {
+        let dir = self.backup_dir();
+        if !dir.exists() {
+            return Ok(vec![]);
+        }
+        let mut backups: Vec<_> = dir
+            .read_dir()?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| BackupFile::from_path(e.path()))
+            .collect();
+        backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+        Ok(backups)
+    }
+
+    /// Replaces the database contents with the given backup. The current
+    /// state is backed up before and returned.
+    pub fn restore_backup(&self, backup: &Path) -> Result<BackupFile> {
+        if !backup.is_file() {
+            return Err(anyhow::anyhow!("no backup at {}", backup.display()));
+        }
+        let previous = self.backup("restore")?;
+
+        // Restoring needs exclusive access to a connection, so a second one
+        // is opened. The backup may also predate later schema migrations.
+        let mut conn = super::open(&self.path())?;
+        conn.restore(MAIN_DB, backup, None::<fn(_)>)?;
+        MIGRATIONS.to_latest(&mut conn)?;
+
+        Ok(previous)
+    }
+
+    /// Removes all backups taken before the given timestamp and returns
+    /// their count.
+    fn prune_backups(&self, before: NaiveDateTime) -> Result<usize> {
+        let old: Vec<_> = self
+            .backups()?
+            .into_iter()
+            .filter(|b| b.created_at < before)
+            .collect();
+        for backup in &old {
+            fs::remove_file(&backup.path)?;
+        }
+        Ok(old.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BackupFile;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parse_backup_name() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let path = dir.join("20250724-093000.123-pre-restore.sqlite");
+        std::fs::write(&path, "data").unwrap();
+
+        let backup = BackupFile::from_path(path.clone()).unwrap();
+        assert_eq!("pre-restore", backup.reason);
+        assert_eq!("2025-07-24 09:30:00.123", backup.created_at.to_string());
+        assert_eq!(4, backup.size);
+
+        assert!(BackupFile::from_path(PathBuf::from("20250724-093000.123.sqlite")).is_none());
+        assert!(BackupFile::from_path(dir.join("notes.txt")).is_none());
+    }
+}
@@ -0,0 +1,148 @@
+use super::Database;
+use crate::model::Entry;
+use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
+use rusqlite::{OptionalExtension, ToSql, params};
+use std::fmt;
+
+/// The kind of change recorded in the history of an entry.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HistoryAction {
+    /// State of the entry when the history has been introduced.
+    Import,
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Purge,
+}
+
+impl HistoryAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Import => "import",
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::Restore => "restore",
+            Self::Purge => "purge",
+        }
+    }
+}
+
+impl fmt::Display for HistoryAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ToSql for HistoryAction {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(self.as_str().into())
+    }
+}
+
+impl FromSql for HistoryAction {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Ok(match value.as_str()? {
+            "import" => Self::Import,
+            "create" => Self::Create,
+            "update" => Self::Update,
+            "delete" => Self::Delete,
+            "restore" => Self::Restore,
+            "purge" => Self::Purge,
+            v => return Err(FromSqlError::Other(format!("invalid action: {v}").into())),
+        })
+    }
+}
+
+/// The state of an entry after a recorded change.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub changed_at: NaiveDateTime,
+    pub action: HistoryAction,
+    pub entry: Entry,
+    /// Whether the change affected a locked period.
+    pub forced: bool,
+}
+
+impl Database {
+    /// Records the current state of the entry with the given ID in its
+    /// history. `forced` marks changes overriding a lock.
+    pub(super) fn record_history(
+        &self,
+        id: u32,
+        action: HistoryAction,
+        changed_at: NaiveDateTime,
+        forced: bool,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO entry_history (
+                entry_id, changed_at, action, date, time, message, long, forced
+            )
+            SELECT id, ?, ?, date, time, message, long, ?
+            FROM entry WHERE id = ?",
+            params![changed_at, action, forced, id],
+        )?;
+        Ok(())
+    }
+
+    /// Returns all recorded versions of the entry with the given ID,
+    /// oldest first.
+    pub fn history(&self, id: u32) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT entry_id, datetime(date || ' ' || time), message, long, changed_at, action, forced
+            FROM entry_history WHERE entry_id = ?
+            ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![id], |row| {
+            Ok(HistoryEntry {
+                entry: Entry {
+                    id: row.get(0)?,
+                    timestamp: row.get(1)?,
+                    message: row.get(2)?,
+                    long: row.get(3)?,
+                },
+                changed_at: row.get(4)?,
+                action: row.get(5)?,
+                forced: row.get(6)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Returns the IDs of all entries which had a version dated within the
+    /// given range or, if `changed` is set, which have been changed within
+    /// the given range.
+    pub fn history_ids(&self, from: NaiveDate, to: NaiveDate, changed: bool) -> Result<Vec<u32>> {
+        let column = match changed {
+            true => "date(changed_at)",
+            false => "date",
+        };
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT entry_id
+            FROM entry_history WHERE {column} BETWEEN ? AND ?
+            GROUP BY entry_id
+            ORDER BY min(date || ' ' || time), entry_id"
+        ))?;
+        let rows = stmt.query_map(params![from, to], |row| row.get(0))?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Returns when the entry with the given ID has been created and last
+    /// updated, if it still exists and these are known.
+    pub fn entry_times(
+        &self,
+        id: u32,
+    ) -> Result<Option<(Option<NaiveDateTime>, Option<NaiveDateTime>)>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT created_at, updated_at FROM entry WHERE id = ?",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?)
+    }
+}
@@ -0,0 +1,119 @@
+use super::Database;
+use crate::clock;
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use rusqlite::params;
+use std::collections::HashSet;
+
+/// An issued invoice. The total is stored in cents.
+#[derive(Clone, Debug)]
+pub struct InvoiceRecord {
+    pub id: u32,
+    pub number: String,
+    pub client: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub issued_at: NaiveDateTime,
+    pub total: i64,
+    pub currency: String,
+}
+
+/// An invoice to be recorded. The total is given in cents.
+#[derive(Clone, Debug)]
+pub struct NewInvoice {
+    pub client: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub total: i64,
+    pub currency: String,
+    pub entry_ids: Vec<u32>,
+}
+
+/// Returns the number of the next invoice issued at the given time. Invoices
+/// are numbered sequentially per year, e.g. `2025-0001`.
+pub(crate) fn next_invoice_number(issued_at: NaiveDateTime, issued: &[InvoiceRecord]) -> String {
+    let prefix = format!("{}-", issued_at.year());
+    let count = issued
+        .iter()
+        .filter(|i| i.number.starts_with(&prefix))
+        .count();
+    format!("{prefix}{:04}", count + 1)
+}
+
+impl Database {
+    /// Records an invoice over the given entries, so that they are not billed
+    /// again.
+    pub fn record_invoice(&self, invoice: &NewInvoice) -> Result<InvoiceRecord> {
+        self.transaction(|db| {
+            let invoiced = db.invoiced_entries()?;
+            if let Some(id) = invoice.entry_ids.iter().find(|id| invoiced.contains(id)) {
+                return Err(anyhow::anyhow!("entry {id} has already been invoiced"));
+            }
+
+            let issued_at = clock::now();
+            let number = next_invoice_number(issued_at, &db.invoices()?);
+            let NewInvoice {
+                ref client,
+                start,
+                end,
+                total,
+                ref currency,
+                ref entry_ids,
+            } = *invoice;
+
+            db.conn.execute(
+                "INSERT INTO invoice
+                (number, client, start_date, end_date, issued_at, total, currency)
+                VALUES (?, ?, ?, ?, ?, ?, ?)",
+                params![number, client, start, end, issued_at, total, currency],
+            )?;
+            let id = db.conn.last_insert_rowid() as u32;
+
+            for entry_id in entry_ids {
+                db.conn.execute(
+                    "INSERT INTO invoice_entry (invoice_id, entry_id) VALUES (?, ?)",
+                    params![id, entry_id],
+                )?;
+            }
+
+            Ok(InvoiceRecord {
+                id,
+                number,
+                client: client.clone(),
+                start,
+                end,
+                issued_at,
+                total,
+                currency: currency.clone(),
+            })
+        })
+    }
+
+    /// Returns all invoices in the order they have been issued.
+    pub fn invoices(&self) -> Result<Vec<InvoiceRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, number, client, start_date, end_date, issued_at, total, currency
+            FROM invoice ORDER BY id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(InvoiceRecord {
+                id: row.get(0)?,
+                number: row.get(1)?,
+                client: row.get(2)?,
+                start: row.get(3)?,
+                end: row.get(4)?,
+                issued_at: row.get(5)?,
+                total: row.get(6)?,
+                currency: row.get(7)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Returns the IDs of all entries which have already been invoiced.
+    pub fn invoiced_entries(&self) -> Result<HashSet<u32>> {
+        let mut stmt = self.conn.prepare("SELECT entry_id FROM invoice_entry")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        Ok(rows.collect::<Result<HashSet<_>, _>>()?)
+    }
+}
@@ -0,0 +1,120 @@
+use super::Database;
+use crate::clock;
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use rusqlite::{OptionalExtension, params};
+
+/// A period in which entries can not be changed without force.
+#[derive(Clone, Debug)]
+pub struct Lock {
+    pub id: u32,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub locked_at: NaiveDateTime,
+}
+
+impl Database {
+    /// Allows changing entries of locked periods. Such changes are marked
+    /// as forced in the history of the entries.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Locks all days within the given period.
+    pub fn lock(&self, start: NaiveDate, end: NaiveDate) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO lock (start_date, end_date, locked_at)
+            VALUES (?, ?, ?)",
+            params![start, end, clock::now()],
+        )?;
+        Ok(())
+    }
+
+    /// Unlocks all days within the given period. Locks only partially
+    /// covered by the period are shrunk accordingly.
+    pub fn unlock(&self, start: NaiveDate, end: NaiveDate) -> Result<usize> {
+        self.transaction(|db| {
+            let overlapping: Vec<_> = db
+                .locks()?
+                .into_iter()
+                .filter(|l| l.start <= end && l.end >= start)
+                .collect();
+
+            for lock in &overlapping {
+                db.conn
+                    .execute("DELETE FROM lock WHERE id = ?", params![lock.id])?;
+
+                let remaining = [
+                    (lock.start, start - Duration::days(1)),
+                    (end + Duration::days(1), lock.end),
+                ];
+                for (s, e) in remaining.into_iter().filter(|(s, e)| s <= e) {
+                    db.conn.execute(
+                        "INSERT INTO lock (start_date, end_date, locked_at)
+                        VALUES (?, ?, ?)",
+                        params![s, e, lock.locked_at],
+                    )?;
+                }
+            }
+
+            Ok(overlapping.len())
+        })
+    }
+
+    /// Returns all locked periods ordered by date.
+    pub fn locks(&self) -> Result<Vec<Lock>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, start_date, end_date, locked_at
+            FROM lock ORDER BY start_date, end_date",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(Lock {
+                id: row.get(0)?,
+                start: row.get(1)?,
+                end: row.get(2)?,
+                locked_at: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Returns an error if any of the given dates is locked, unless changes
+    /// are forced. Returns whether a locked date is changed by force.
+    pub(super) fn check_locked(&self, dates: &[NaiveDate]) -> Result<bool> {
+        let mut forced = false;
+        for &date in dates {
+            let lock: Option<(NaiveDate, NaiveDate)> = self
+                .conn
+                .query_row(
+                    "SELECT start_date, end_date FROM lock
+                    WHERE start_date <= ?1 AND end_date >= ?1
+                    LIMIT 1",
+                    params![date],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            if let Some((start, end)) = lock {
+                if !self.force {
+                    return Err(anyhow::anyhow!(
+                        "{date} is locked by the period {start}..{end}; \
+                        use --force to change it anyway"
+                    ));
+                }
+                forced = true;
+            }
+        }
+        Ok(forced)
+    }
+
+    /// Returns the date of the entry with the given ID, including entries
+    /// in the trash.
+    pub(super) fn entry_date(&self, id: u32) -> Result<Option<NaiveDate>> {
+        Ok(self
+            .conn
+            .query_row("SELECT date FROM entry WHERE id = ?", params![id], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+}
@@ -0,0 +1,156 @@
+use super::{DB_FILE, Database, MIGRATIONS};
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// An entry which violates the assumptions of the tracker.
+#[derive(Clone, Debug)]
+pub struct EntryProblem {
+    pub entry_id: u32,
+    pub problems: Vec<String>,
+}
+
+/// Statistics about the database.
+#[derive(Clone, Debug)]
+pub struct Stats {
+    pub entries: u32,
+    pub deleted: u32,
+    pub first: Option<NaiveDate>,
+    pub last: Option<NaiveDate>,
+    pub file_size: u64,
+    pub schema_version: usize,
+}
+
+impl Database {
+    /// Returns the path of the database file.
+    pub fn path(&self) -> PathBuf {
+        self.base_dir.join(DB_FILE)
+    }
+
+    /// Runs SQLite's integrity check and returns all reported problems.
+    pub fn integrity_check(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let problems = rows.collect::<Result<Vec<_>, _>>()?;
+        Ok(problems.into_iter().filter(|p| p != "ok").collect())
+    }
+
+    /// Returns all entries, including deleted ones, with invalid dates or
+    /// times or empty messages.
+    pub fn check_entries(&self) -> Result<Vec<EntryProblem>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, quote(date), quote(time),
+                date(date) IS NOT date, time(time) IS NULL, trim(message) = ''
+            FROM entry
+            WHERE date(date) IS NOT date OR time(time) IS NULL OR trim(message) = ''
+            ORDER BY id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let date: String = row.get(1)?;
+            let time: String = row.get(2)?;
+            let problems: [(bool, String); 3] = [
+                (row.get(3)?, format!("invalid date {date}")),
+                (row.get(4)?, format!("invalid time {time}")),
+                (row.get(5)?, "empty message".to_string()),
+            ];
+            Ok(EntryProblem {
+                entry_id: row.get(0)?,
+                problems: problems
+                    .into_iter()
+                    .filter_map(|(found, problem)| found.then_some(problem))
+                    .collect(),
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Rebuilds the database file to reclaim unused space. Returns the size
+    /// before and after, see [`Database::size`].
+    pub fn vacuum(&self) -> Result<(u64, u64)> {
+        let before = self.size()?;
+        self.conn.execute("VACUUM", [])?;
+        // The rebuilt database is written to the write-ahead log first.
+        self.conn
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        let after = self.size()?;
+        Ok((before, after))
+    }
+
+    /// Returns the size of the database file together with its write-ahead
+    /// log and shared memory files, which hold changes not yet written back
+    /// to the database file.
+    fn size(&self) -> Result<u64> {
+        let mut size = fs::metadata(self.path())?.len();
+        for suffix in ["-wal", "-shm"] {
+            let mut path = self.path().into_os_string();
+            path.push(suffix);
+            match fs::metadata(path) {
+                Ok(metadata) => size += metadata.len(),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(size)
+    }
+
+    /// Collects statistics about the stored entries and the database files.
+    pub fn stats(&self) -> Result<Stats> {
+        let (entries, deleted, first, last) = self.conn.query_row(
+            "SELECT
+                count(*) FILTER (WHERE deleted_at IS NULL),
+                count(*) FILTER (WHERE deleted_at IS NOT NULL),
+                min(date) FILTER (WHERE deleted_at IS NULL AND date(date) IS date),
+                max(date) FILTER (WHERE deleted_at IS NULL AND date(date) IS date)
+            FROM entry",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+
+        Ok(Stats {
+            entries,
+            deleted,
+            first,
+            last,
+            file_size: self.size()?,
+            schema_version: (&MIGRATIONS.current_version(&self.conn)?).into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::Database;
+    use crate::model::NewEntry;
+    use chrono::NaiveDateTime;
+    use std::fs;
+
+    #[test]
+    fn sizes_include_write_ahead_log() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = Database::new(tmp.path()).unwrap();
+        for i in 0..100 {
+            db.add(NewEntry {
+                timestamp: NaiveDateTime::parse_from_str("2025-07-24 09:00", "%Y-%m-%d %H:%M")
+                    .unwrap(),
+                message: format!("entry {i}"),
+                long: None,
+            })
+            .unwrap();
+        }
+
+        let len = |name: &str| fs::metadata(tmp.path().join(name)).map_or(0, |m| m.len());
+        let wal = len("db.sqlite-wal");
+        assert!(wal > 0);
+        assert_eq!(
+            len("db.sqlite") + wal + len("db.sqlite-shm"),
+            db.stats().unwrap().file_size
+        );
+
+        let (before, after) = db.vacuum().unwrap();
+        assert!(before > after, "{before} > {after}");
+        assert_eq!(0, len("db.sqlite-wal"));
+        assert_eq!(after, db.stats().unwrap().file_size);
+    }
+}
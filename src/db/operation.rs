@@ -0,0 +1,231 @@
+use super::{Database, HistoryAction};
+use crate::clock;
+use crate::model::Entry;
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use rusqlite::{Row, params};
+
+/// A recorded command execution which can be undone and redone.
+#[derive(Clone, Debug)]
+pub struct Operation {
+    pub id: u32,
+    pub created_at: NaiveDateTime,
+    pub description: String,
+}
+
+/// A recorded change of a single entry. A `None` state denotes that the
+/// entry did not exist before or after the change.
+#[derive(Clone, Debug)]
+pub struct EntryChange {
+    pub entry_id: u32,
+    pub old: Option<Entry>,
+    pub new: Option<Entry>,
+}
+
+impl Database {
+    /// Starts a new operation in the log and returns its ID. Operations
+    /// which have been undone before can not be redone afterwards.
+    pub(super) fn begin_operation(&self, description: &str) -> Result<u32> {
+        self.conn.execute(
+            "DELETE FROM operation_change WHERE operation_id IN
+            (SELECT id FROM operation WHERE undone)",
+            [],
+        )?;
+        self.conn
+            .execute("DELETE FROM operation WHERE undone", [])?;
+        self.conn.execute(
+            "INSERT INTO operation (created_at, description)
+            VALUES (?, ?)",
+            params![clock::now(), description],
+        )?;
+        Ok(self.conn.last_insert_rowid() as u32)
+    }
+
+    /// Records the change of a single entry as part of the given operation.
+    pub(super) fn record_change(
+        &self,
+        operation_id: u32,
+        old: Option<&Entry>,
+        new: Option<&Entry>,
+    ) -> Result<()> {
+        let entry_id = old
+            .or(new)
+            .map(|e| e.id)
+            .ok_or_else(|| anyhow::anyhow!("change requires an old or new entry state"))?;
+        self.conn.execute(
+            "INSERT INTO operation_change (
+                operation_id, entry_id,
+                old_timestamp, old_message, old_long,
+                new_timestamp, new_message, new_long
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                operation_id,
+                entry_id,
+                old.map(|e| e.timestamp),
+                old.map(|e| &e.message),
+                old.and_then(|e| e.long.as_ref()),
+                new.map(|e| e.timestamp),
+                new.map(|e| &e.message),
+                new.and_then(|e| e.long.as_ref()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Removes all changes of the entry with the given ID from the log, e.g.
+    /// because it has been purged, as well as operations left without
+    /// changes. Undone operations can not be redone afterwards.
+    pub(super) fn forget_entry(&self, id: u32) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM operation_change
+            WHERE entry_id = ? OR operation_id IN (SELECT id FROM operation WHERE undone)",
+            params![id],
+        )?;
+        self.conn.execute(
+            "DELETE FROM operation
+            WHERE undone OR id NOT IN (SELECT operation_id FROM operation_change)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Reverts the last `count` operations and returns them, latest first.
+    /// Added entries are moved to the trash.
+    pub fn undo(&self, count: usize) -> Result<Vec<Operation>> {
+        if !self.operations(false, count)?.is_empty() {
+            self.backup_outside_transaction("undo")?;
+        }
+        self.transaction(|db| {
+            let ops = db.operations(false, count)?;
+            for op in &ops {
+                for change in db.operation_changes(op.id)?.iter().rev() {
+                    match change.old {
+                        Some(ref entry) => db.restore(entry)?,
+                        None => db.delete(change.entry_id)?,
+                    }
+                }
+                db.conn.execute(
+                    "UPDATE operation SET undone = TRUE WHERE id = ?",
+                    params![op.id],
+                )?;
+            }
+
+            Ok(ops)
+        })
+    }
+
+    /// Re-applies the last `count` undone operations and returns them,
+    /// earliest first.
+    pub fn redo(&self, count: usize) -> Result<Vec<Operation>> {
+        if !self.operations(true, count)?.is_empty() {
+            self.backup_outside_transaction("redo")?;
+        }
+        self.transaction(|db| {
+            let ops = db.operations(true, count)?;
+            for op in &ops {
+                for change in db.operation_changes(op.id)? {
+                    match change.new {
+                        Some(ref entry) => db.restore(entry)?,
+                        None => db.delete(change.entry_id)?,
+                    }
+                }
+                db.conn.execute(
+                    "UPDATE operation SET undone = FALSE WHERE id = ?",
+                    params![op.id],
+                )?;
+            }
+
+            Ok(ops)
+        })
+    }
+
+    /// Returns up to `limit` operations in the order they would be undone
+    /// or, when `undone` is set, redone.
+    pub fn operations(&self, undone: bool, limit: usize) -> Result<Vec<Operation>> {
+        let order = match undone {
+            true => "ASC",
+            false => "DESC",
+        };
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, created_at, description
+            FROM operation WHERE undone = ?
+            ORDER BY id {order} LIMIT ?"
+        ))?;
+        let rows = stmt.query_map(params![undone, limit as i64], |row| {
+            Ok(Operation {
+                id: row.get(0)?,
+                created_at: row.get(1)?,
+                description: row.get(2)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Returns the changes of the given operation in the order they have
+    /// been applied.
+    pub fn operation_changes(&self, operation_id: u32) -> Result<Vec<EntryChange>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT entry_id,
+                old_timestamp, old_message, old_long,
+                new_timestamp, new_message, new_long
+            FROM operation_change WHERE operation_id = ?
+            ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![operation_id], |row| {
+            let entry_id = row.get(0)?;
+            Ok(EntryChange {
+                entry_id,
+                old: entry_state(row, entry_id, 1)?,
+                new: entry_state(row, entry_id, 4)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Sets the entry to the given state, re-creating it if it has been
+    /// removed or moved to the trash.
+    fn restore(&self, entry: &Entry) -> Result<()> {
+        let mut dates = vec![entry.timestamp.date()];
+        dates.extend(self.entry_date(entry.id)?);
+        let forced = self.check_locked(&dates)?;
+        let now = clock::now();
+        let action = match self.get(entry.id)? {
+            Some(_) => HistoryAction::Update,
+            None => HistoryAction::Restore,
+        };
+        self.conn.execute(
+            "INSERT INTO entry (id, date, time, message, long, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+            ON CONFLICT (id) DO UPDATE SET
+                date = excluded.date,
+                time = excluded.time,
+                message = excluded.message,
+                long = excluded.long,
+                updated_at = excluded.updated_at,
+                deleted_at = NULL",
+            params![
+                entry.id,
+                entry.timestamp.date(),
+                entry.timestamp.time(),
+                entry.message,
+                entry.long,
+                now
+            ],
+        )?;
+        self.record_history(entry.id, action, now, forced)
+    }
+}
+
+fn entry_state(row: &Row, id: u32, offset: usize) -> rusqlite::Result<Option<Entry>> {
+    let timestamp: Option<NaiveDateTime> = row.get(offset)?;
+    let Some(timestamp) = timestamp else {
+        return Ok(None);
+    };
+    Ok(Some(Entry {
+        id,
+        timestamp,
+        message: row.get(offset + 1)?,
+        long: row.get(offset + 2)?,
+    }))
+}
@@ -0,0 +1,275 @@
+use super::Database;
+use crate::clock;
+use crate::model::Entry;
+use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::{OptionalExtension, params};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Hash used as previous hash of the first seal in the chain.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// A closed period of entries, linked to the previous seal via its hash.
+#[derive(Clone, Debug)]
+pub struct SealedPeriod {
+    pub id: u32,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub sealed_at: NaiveDateTime,
+    pub previous_hash: String,
+    pub hash: String,
+}
+
+/// A deviation of the stored entries or seals from their sealed state.
+#[derive(Clone, Debug)]
+pub enum SealViolation {
+    /// The seal itself or its link to the previous seal has been altered.
+    BrokenChain { seal: SealedPeriod },
+    /// A sealed entry has been changed after sealing.
+    Changed { seal: SealedPeriod, entry: Entry },
+    /// A sealed entry has been deleted after sealing.
+    Removed { seal: SealedPeriod, entry_id: u32 },
+    /// An entry has been added to the period after sealing.
+    Added { seal: SealedPeriod, entry: Entry },
+}
+
+impl Database {
+    /// Seals all entries within the given period by storing their hashes,
+    /// chained to the hash of the latest seal.
+    pub fn seal(&self, start: NaiveDate, end: NaiveDate) -> Result<SealedPeriod> {
+        self.transaction(|db| {
+            let overlapping: Option<(NaiveDate, NaiveDate)> = db
+                .conn
+                .query_row(
+                    "SELECT start_date, end_date FROM seal
+                    WHERE start_date <= ? AND end_date >= ?
+                    LIMIT 1",
+                    params![end, start],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            if let Some((s, e)) = overlapping {
+                return Err(anyhow::anyhow!(
+                    "the period overlaps with the already sealed period {s}..{e}"
+                ));
+            }
+
+            let previous_hash = db
+                .seals()?
+                .pop()
+                .map(|s| s.hash)
+                .unwrap_or_else(|| GENESIS_HASH.to_string());
+
+            let entry_hashes: Vec<_> = db
+                .list_range(start, end)?
+                .iter()
+                .map(|e| (e.id, entry_hash(e)))
+                .collect();
+
+            let sealed_at = clock::now();
+            let hash = seal_hash(&previous_hash, start, end, sealed_at, &entry_hashes);
+
+            db.conn.execute(
+                "INSERT INTO seal (start_date, end_date, sealed_at, previous_hash, hash)
+                VALUES (?, ?, ?, ?, ?)",
+                params![start, end, sealed_at, previous_hash, hash],
+            )?;
+            let id = db.conn.last_insert_rowid() as u32;
+
+            for (entry_id, entry_hash) in &entry_hashes {
+                db.conn.execute(
+                    "INSERT INTO seal_entry (seal_id, entry_id, hash)
+                    VALUES (?, ?, ?)",
+                    params![id, entry_id, entry_hash],
+                )?;
+            }
+
+            Ok(SealedPeriod {
+                id,
+                start,
+                end,
+                sealed_at,
+                previous_hash,
+                hash,
+            })
+        })
+    }
+
+    /// Returns all seals in the order they have been created.
+    pub fn seals(&self) -> Result<Vec<SealedPeriod>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, start_date, end_date, sealed_at, previous_hash, hash
+            FROM seal ORDER BY id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(SealedPeriod {
+                id: row.get(0)?,
+                start: row.get(1)?,
+                end: row.get(2)?,
+                sealed_at: row.get(3)?,
+                previous_hash: row.get(4)?,
+                hash: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Verifies the seal chain and all sealed entries against their
+    /// current state and returns all found violations.
+    pub fn verify_seals(&self) -> Result<Vec<SealViolation>> {
+        let mut violations = vec![];
+        let mut previous_hash = GENESIS_HASH.to_string();
+
+        for seal in self.seals()? {
+            let sealed = self.sealed_entries(seal.id)?;
+
+            let hash = seal_hash(
+                &seal.previous_hash,
+                seal.start,
+                seal.end,
+                seal.sealed_at,
+                &sealed,
+            );
+            if seal.previous_hash != previous_hash || seal.hash != hash {
+                violations.push(SealViolation::BrokenChain { seal: seal.clone() });
+            }
+            previous_hash = seal.hash.clone();
+
+            let mut current: HashMap<u32, Entry> = self
+                .list_range(seal.start, seal.end)?
+                .into_iter()
+                .map(|e| (e.id, e))
+                .collect();
+
+            for (entry_id, hash) in sealed {
+                match current.remove(&entry_id) {
+                    Some(entry) if entry_hash(&entry) != hash => {
+                        violations.push(SealViolation::Changed {
+                            seal: seal.clone(),
+                            entry,
+                        });
+                    }
+                    Some(_) => {}
+                    None => violations.push(SealViolation::Removed {
+                        seal: seal.clone(),
+                        entry_id,
+                    }),
+                }
+            }
+
+            let mut added: Vec<_> = current.into_values().collect();
+            added.sort_by_key(|e| e.timestamp);
+            violations.extend(added.into_iter().map(|entry| SealViolation::Added {
+                seal: seal.clone(),
+                entry,
+            }));
+        }
+
+        Ok(violations)
+    }
+
+    fn sealed_entries(&self, seal_id: u32) -> Result<Vec<(u32, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT entry_id, hash FROM seal_entry
+            WHERE seal_id = ?
+            ORDER BY rowid",
+        )?;
+        let rows = stmt.query_map(params![seal_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+/// Hashes all fields of the given entry.
+fn entry_hash(entry: &Entry) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(entry.id.to_be_bytes());
+    hasher.update(entry.timestamp.format("%Y-%m-%dT%H:%M:%S%.f").to_string());
+    hasher.update([0x1f]);
+    hasher.update(&entry.message);
+    hasher.update([0x1f]);
+    if let Some(ref long) = entry.long {
+        hasher.update([0x01]);
+        hasher.update(long);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Hashes a seal by chaining the previous seal hash with the sealed period,
+/// the time of sealing and the hashes of all sealed entries in order.
+fn seal_hash(
+    previous_hash: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+    sealed_at: NaiveDateTime,
+    entry_hashes: &[(u32, String)],
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(previous_hash);
+    hasher.update(format!("{start}..{end}"));
+    hasher.update([0x1f]);
+    hasher.update(sealed_at.format("%Y-%m-%dT%H:%M:%S%.f").to_string());
+    for (id, hash) in entry_hashes {
+        hasher.update(id.to_be_bytes());
+        hasher.update(hash);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GENESIS_HASH, entry_hash, seal_hash};
+    use crate::model::Entry;
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    fn entry() -> Entry {
+        Entry {
+            id: 1,
+            timestamp: NaiveDateTime::parse_from_str("2025-07-24 09:00:00", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            message: "start".to_string(),
+            long: None,
+        }
+    }
+
+    #[test]
+    fn entry_hash_covers_all_fields() {
+        let hash = entry_hash(&entry());
+        assert_eq!(hash, entry_hash(&entry()));
+        assert_eq!(64, hash.len());
+
+        let changed = [
+            Entry { id: 2, ..entry() },
+            Entry {
+                timestamp: entry().timestamp + chrono::Duration::minutes(1),
+                ..entry()
+            },
+            Entry {
+                message: "end".to_string(),
+                ..entry()
+            },
+            Entry {
+                long: Some(String::new()),
+                ..entry()
+            },
+        ];
+        for e in changed {
+            assert_ne!(hash, entry_hash(&e));
+        }
+    }
+
+    #[test]
+    fn seal_hash_is_chained() {
+        let date = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+        let sealed_at = entry().timestamp;
+        let entries = vec![(1, entry_hash(&entry()))];
+
+        let first = seal_hash(GENESIS_HASH, date, date, sealed_at, &entries);
+        let second = seal_hash(&first, date, date, sealed_at, &entries);
+
+        assert_ne!(first, second);
+        assert_ne!(first, seal_hash(GENESIS_HASH, date, date, sealed_at, &[]));
+        let later = sealed_at + chrono::Duration::seconds(1);
+        assert_ne!(first, seal_hash(GENESIS_HASH, date, date, later, &entries));
+    }
+}
@@ -0,0 +1,122 @@
+use super::{Database, HistoryAction, entry_from_row};
+use crate::clock;
+use crate::model::{DeletedEntry, Entry};
+use anyhow::Result;
+use chrono::{Duration, NaiveDateTime};
+use rusqlite::params;
+
+impl Database {
+    /// Returns all entries in the trash, latest deleted first.
+    pub fn list_deleted(&self) -> Result<Vec<DeletedEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, datetime(date || ' ' || time) as timestamp, message, long, deleted_at
+            FROM entry WHERE deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC, date, time",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(DeletedEntry {
+                entry: entry_from_row(row)?,
+                deleted_at: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Moves the entries with the given IDs out of the trash and records
+    /// this as one operation, which can be undone. Fails without restoring
+    /// any entry if one of them is not in the trash.
+    pub fn restore_deleted(&self, ids: &[u32]) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.transaction(|db| {
+            let deleted = db.list_deleted()?;
+            let entries = ids
+                .iter()
+                .map(|&id| {
+                    deleted
+                        .iter()
+                        .find(|d| d.entry.id == id)
+                        .map(|d| d.entry.clone())
+                        .ok_or_else(|| anyhow::anyhow!("no entry with id {id} in the trash"))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let operation_id = db.begin_operation(&restore_description(&entries))?;
+            for entry in &entries {
+                let forced = db.check_locked(&[entry.timestamp.date()])?;
+                let now = clock::now();
+                db.conn.execute(
+                    "UPDATE entry
+                    SET deleted_at = NULL, updated_at = ?
+                    WHERE id = ?",
+                    params![now, entry.id],
+                )?;
+                db.record_history(entry.id, HistoryAction::Restore, now, forced)?;
+                db.record_change(operation_id, None, Some(entry))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Permanently removes the entry with the given ID. Its changes are
+    /// removed from the operation log, as they can not be undone anymore.
+    pub fn purge(&self, id: u32) -> Result<()> {
+        self.transaction(|db| {
+            let forced = db.check_locked(db.entry_date(id)?.as_slice())?;
+            db.record_history(id, HistoryAction::Purge, clock::now(), forced)?;
+            db.forget_entry(id)?;
+            db.conn.execute(
+                "DELETE FROM entry
+                WHERE id = ?",
+                params![id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Permanently removes all entries which have been in the trash for
+    /// longer than the retention and returns their count.
+    pub fn purge_expired(&self, retention: Duration) -> Result<usize> {
+        self.purge_deleted_before(clock::now() - retention)
+    }
+
+    /// Permanently removes all entries which have been moved to the trash at
+    /// or before the given timestamp and returns their count. Entries of
+    /// locked days are kept unless changes are forced. A backup is taken
+    /// before if there are any entries to remove.
+    pub fn purge_deleted_before(&self, timestamp: NaiveDateTime) -> Result<usize> {
+        if !self
+            .list_deleted()?
+            .iter()
+            .any(|d| d.deleted_at <= timestamp)
+        {
+            return Ok(0);
+        }
+        self.backup_outside_transaction("purge")?;
+        self.transaction(|db| {
+            let mut stmt = db.conn.prepare(
+                "SELECT id FROM entry
+                WHERE deleted_at IS NOT NULL AND deleted_at <= ?
+                AND (? OR NOT EXISTS (
+                    SELECT 1 FROM lock WHERE date BETWEEN start_date AND end_date
+                ))",
+            )?;
+            let ids = stmt
+                .query_map(params![timestamp, db.force], |row| row.get(0))?
+                .collect::<Result<Vec<u32>, _>>()?;
+            for &id in &ids {
+                db.purge(id)?;
+            }
+            Ok(ids.len())
+        })
+    }
+}
+
+/// Describes the restoring of the given entries in the operation log.
+pub(crate) fn restore_description(entries: &[Entry]) -> String {
+    match entries {
+        [entry] => format!("restore {}", entry.summary()),
+        _ => format!("restore {} entries", entries.len()),
+    }
+}
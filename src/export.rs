@@ -0,0 +1,13 @@
+//! Conversion of tracked entries into the formats of other tools.
+
+mod ics;
+mod journal;
+mod ledger;
+pub(crate) mod timew;
+mod xlsx;
+
+pub use ics::write_ics;
+pub use journal::{write_markdown, write_org};
+pub use ledger::write_ledger;
+pub use timew::write_timew;
+pub use xlsx::write_xlsx;
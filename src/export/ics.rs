@@ -0,0 +1,176 @@
+use crate::clock;
+use crate::config::Config;
+use crate::model::Entry;
+use crate::summary::intervals;
+use anyhow::Result;
+use chrono::{Local, NaiveDateTime, Utc};
+use std::fmt::Display;
+use std::io::Write;
+
+const PACKAGE: &str = env!("CARGO_PKG_NAME");
+const PRODID: &str = concat!(
+    "-//",
+    env!("CARGO_PKG_NAME"),
+    "//",
+    env!("CARGO_PKG_VERSION"),
+    "//EN"
+);
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// Maximum length of a content line in octets, excluding the line break.
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Writes the intervals between the given entries as iCalendar events.
+///
+/// Each event is identified by the ID of the entry describing it, so that
+/// exporting the same entries again updates the events in a calendar.
+/// Times are written in local time without a time zone.
+pub fn write_ics<W: Write>(
+    mut w: W,
+    entries: &[Entry],
+    config: &Config,
+    include_breaks: bool,
+) -> Result<()> {
+    let stamp = utc(clock::now()).format("%Y%m%dT%H%M%SZ").to_string();
+
+    write_line(&mut w, "BEGIN:VCALENDAR")?;
+    write_property(&mut w, "VERSION", "2.0")?;
+    write_property(&mut w, "PRODID", PRODID)?;
+    write_property(&mut w, "CALSCALE", "GREGORIAN")?;
+
+    for interval in intervals(entries) {
+        if !include_breaks && interval.is_break(config)? {
+            continue;
+        }
+        let entry = interval.entry;
+
+        write_line(&mut w, "BEGIN:VEVENT")?;
+        write_property(&mut w, "UID", format_args!("entry-{}@{PACKAGE}", entry.id))?;
+        write_property(&mut w, "DTSTAMP", &stamp)?;
+        write_property(&mut w, "DTSTART", interval.start.format(DATE_TIME_FORMAT))?;
+        write_property(&mut w, "DTEND", interval.end.format(DATE_TIME_FORMAT))?;
+        write_property(&mut w, "SUMMARY", escape(&entry.message))?;
+        if let Some(ref long) = entry.long {
+            write_property(&mut w, "DESCRIPTION", escape(long))?;
+        }
+        write_line(&mut w, "END:VEVENT")?;
+    }
+
+    write_line(&mut w, "END:VCALENDAR")?;
+    Ok(())
+}
+
+/// Converts a local time to UTC, taking it as UTC if it does not exist in
+/// the local time zone.
+fn utc(time: NaiveDateTime) -> NaiveDateTime {
+    time.and_local_timezone(Local)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc).naive_utc())
+        .unwrap_or(time)
+}
+
+/// Escapes a text value as defined in RFC 5545, section 3.3.11.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn write_property<W: Write>(w: &mut W, name: &str, value: impl Display) -> Result<()> {
+    write_line(w, &format!("{name}:{value}"))
+}
+
+/// Writes a content line, folding it after each 75 octets without
+/// splitting characters.
+fn write_line<W: Write>(w: &mut W, line: &str) -> Result<()> {
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            w.write_all(b"\r\n ")?;
+            // The leading space counts towards the folded line.
+            length = 1;
+        }
+        write!(w, "{c}")?;
+        length += c.len_utf8();
+    }
+    w.write_all(b"\r\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{escape, write_ics, write_line};
+    use crate::config::Config;
+    use crate::model::Entry;
+    use chrono::NaiveDateTime;
+    use figment::Figment;
+
+    fn entry(id: u32, time: &str, message: &str, long: Option<&str>) -> Entry {
+        Entry {
+            id,
+            timestamp: NaiveDateTime::parse_from_str(
+                &format!("2025-07-24 {time}"),
+                "%Y-%m-%d %H:%M",
+            )
+            .unwrap(),
+            message: message.to_string(),
+            long: long.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn escape_and_fold() {
+        assert_eq!("a\\, b\\; c\\\\d\\ne", escape("a, b; c\\d\ne"));
+
+        let mut out = vec![];
+        write_line(&mut out, &"x".repeat(80)).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(format!("{}\r\n {}\r\n", "x".repeat(75), "x".repeat(5)), out);
+
+        let mut out = vec![];
+        write_line(&mut out, &"ä".repeat(40)).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.lines().all(|l| l.trim_end().len() <= 75));
+        assert_eq!("ä".repeat(40), out.replace("\r\n ", "").trim_end());
+    }
+
+    #[test]
+    fn events_from_intervals() {
+        let config: Config = Figment::new().extract().unwrap();
+        let entries = vec![
+            entry(1, "09:00", "start", None),
+            entry(2, "12:00", "coding, reviews", Some("PR #42\nPR #43")),
+            entry(3, "12:30", "break", None),
+            entry(4, "17:00", "end", None),
+        ];
+
+        let mut out = vec![];
+        write_ics(&mut out, &entries, &config, false).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(out.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(2, out.matches("BEGIN:VEVENT").count());
+        assert!(out.contains(
+            "UID:entry-2@tracker\r\n\
+            DTSTAMP:"
+        ));
+        assert!(out.contains(
+            "DTSTART:20250724T090000\r\n\
+            DTEND:20250724T120000\r\n\
+            SUMMARY:coding\\, reviews\r\n\
+            DESCRIPTION:PR #42\\nPR #43\r\n\
+            END:VEVENT"
+        ));
+        assert!(out.contains("DTSTART:20250724T123000\r\nDTEND:20250724T170000\r\nSUMMARY:end"));
+        assert!(!out.contains("SUMMARY:break"));
+
+        let mut out = vec![];
+        write_ics(&mut out, &entries, &config, true).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(3, out.matches("BEGIN:VEVENT").count());
+        assert!(out.contains("SUMMARY:break"));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::config::Config;
+use crate::model::Entry;
+use crate::summary::{DaySummary, intervals};
+use anyhow::Result;
+use chrono::Duration;
+use fancy_duration::AsFancyDuration;
+use std::io::Write;
+
+/// Writes one org-mode heading per day with a `CLOCK:` line for each
+/// worked interval and the daily totals. Long descriptions are nested
+/// below the clock lines and breaks are only counted in the totals.
+pub fn write_org<W: Write>(mut w: W, entries: &[Entry], config: &Config) -> Result<()> {
+    for day in days(entries) {
+        let summary = DaySummary::compute(day, config)?;
+        writeln!(w, "* {}", day[0].timestamp.format("%Y-%m-%d %A"))?;
+        writeln!(
+            w,
+            "  Total: {} (pause {})",
+            org_duration(summary.work),
+            org_duration(summary.pause)
+        )?;
+
+        for interval in intervals(day) {
+            if interval.is_break(config)? {
+                continue;
+            }
+            writeln!(w, "** {}", interval.entry.message)?;
+            writeln!(
+                w,
+                "   CLOCK: [{}]--[{}] => {:>5}",
+                interval.start.format("%Y-%m-%d %a %H:%M"),
+                interval.end.format("%Y-%m-%d %a %H:%M"),
+                org_duration(interval.duration())
+            )?;
+            if let Some(ref long) = interval.entry.long {
+                for line in long.lines() {
+                    writeln!(w, "   {line}")?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes one Markdown section per day with a table of all intervals and
+/// the daily totals. Long descriptions are put below the message.
+pub fn write_markdown<W: Write>(mut w: W, entries: &[Entry], config: &Config) -> Result<()> {
+    for (i, day) in days(entries).enumerate() {
+        if i > 0 {
+            writeln!(w)?;
+        }
+        writeln!(w, "## {}\n", day[0].timestamp.format("%A, %-d %B %Y"))?;
+        writeln!(w, "| Start | End | Duration | Message |")?;
+        writeln!(w, "| ----- | --- | -------- | ------- |")?;
+
+        for interval in intervals(day) {
+            let entry = interval.entry;
+            let mut message = escape_cell(&entry.message);
+            if interval.is_break(config)? {
+                message = format!("*{message}*");
+            }
+            if let Some(ref long) = entry.long {
+                message = format!("{message}<br>{}", escape_cell(long));
+            }
+            writeln!(
+                w,
+                "| {} | {} | {} | {message} |",
+                interval.start.format("%H:%M"),
+                interval.end.format("%H:%M"),
+                fancy(interval.duration())
+            )?;
+        }
+
+        let summary = DaySummary::compute(day, config)?;
+        writeln!(
+            w,
+            "\n**Total:** {} ({} pause)",
+            fancy(summary.work),
+            fancy(summary.pause)
+        )?;
+    }
+    Ok(())
+}
+
+fn days(entries: &[Entry]) -> impl Iterator<Item = &[Entry]> {
+    entries.chunk_by(|a, b| a.timestamp.date() == b.timestamp.date())
+}
+
+/// Formats a duration like org-mode, e.g. `3:05`.
+fn org_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+fn fancy(duration: Duration) -> String {
+    duration.fancy_duration().truncate(2).to_string()
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{write_markdown, write_org};
+    use crate::config::Config;
+    use crate::model::Entry;
+    use chrono::NaiveDateTime;
+    use figment::Figment;
+
+    fn entry(date_time: &str, message: &str, long: Option<&str>) -> Entry {
+        Entry {
+            id: 0,
+            timestamp: NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").unwrap(),
+            message: message.to_string(),
+            long: long.map(str::to_string),
+        }
+    }
+
+    fn entries() -> Vec<Entry> {
+        vec![
+            entry("2025-07-24 09:00", "start", None),
+            entry("2025-07-24 12:00", "coding", Some("login | signup\nreview")),
+            entry("2025-07-24 12:45", "break", None),
+            entry("2025-07-24 17:00", "end", None),
+            entry("2025-07-25 08:00", "start", None),
+            entry("2025-07-25 08:30", "mails", None),
+        ]
+    }
+
+    #[test]
+    fn org() {
+        let config: Config = Figment::new().extract().unwrap();
+        let mut out = vec![];
+        write_org(&mut out, &entries(), &config).unwrap();
+        assert_eq!(
+            "* 2025-07-24 Thursday\n  \
+            Total: 7:15 (pause 0:45)\n\
+            ** coding\n   \
+            CLOCK: [2025-07-24 Thu 09:00]--[2025-07-24 Thu 12:00] =>  3:00\n   \
+            login | signup\n   \
+            review\n\
+            ** end\n   \
+            CLOCK: [2025-07-24 Thu 12:45]--[2025-07-24 Thu 17:00] =>  4:15\n\
+            * 2025-07-25 Friday\n  \
+            Total: 0:30 (pause 0:00)\n\
+            ** mails\n   \
+            CLOCK: [2025-07-25 Fri 08:00]--[2025-07-25 Fri 08:30] =>  0:30\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn markdown() {
+        let config: Config = Figment::new().extract().unwrap();
+        let mut out = vec![];
+        write_markdown(&mut out, &entries()[..4], &config).unwrap();
+        assert_eq!(
+            "## Thursday, 24 July 2025\n\n\
+            | Start | End | Duration | Message |\n\
+            | ----- | --- | -------- | ------- |\n\
+            | 09:00 | 12:00 | 3h | coding<br>login \\| signup<br>review |\n\
+            | 12:00 | 12:45 | 45m | *break* |\n\
+            | 12:45 | 17:00 | 4h 15m | end |\n\
+            \n**Total:** 7h 15m (45m pause)\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::config::Config;
+use crate::model::Entry;
+use crate::summary::intervals;
+use anyhow::Result;
+use std::io::Write;
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Writes the intervals between the given entries in the timeclock format
+/// of ledger and hledger.
+///
+/// Each interval is clocked in to the account of the first matching
+/// `ledger_accounts` rule, or `ledger_default_account`, with the message of
+/// the entry describing it. Breaks are clocked out.
+pub fn write_ledger<W: Write>(mut w: W, entries: &[Entry], config: &Config) -> Result<()> {
+    for interval in intervals(entries) {
+        if interval.is_break(config)? {
+            continue;
+        }
+        let entry = interval.entry;
+        writeln!(
+            w,
+            "i {} {}  {}",
+            interval.start.format(TIME_FORMAT),
+            account(entry, config)?,
+            entry.message
+        )?;
+        writeln!(w, "o {}", interval.end.format(TIME_FORMAT))?;
+    }
+    Ok(())
+}
+
+fn account<'c>(entry: &Entry, config: &'c Config) -> Result<&'c str> {
+    for rule in &config.ledger_accounts {
+        if entry.matches(&rule.filter)? {
+            return Ok(&rule.account);
+        }
+    }
+    Ok(&config.ledger_default_account)
+}
+
+#[cfg(test)]
+mod test {
+    use super::write_ledger;
+    use crate::config::Config;
+    use crate::model::Entry;
+    use chrono::NaiveDateTime;
+    use figment::Figment;
+    use figment::providers::{Format, Toml};
+
+    fn entry(time: &str, message: &str) -> Entry {
+        Entry {
+            id: 0,
+            timestamp: NaiveDateTime::parse_from_str(
+                &format!("2025-07-24 {time}"),
+                "%Y-%m-%d %H:%M",
+            )
+            .unwrap(),
+            message: message.to_string(),
+            long: None,
+        }
+    }
+
+    #[test]
+    fn clock_in_and_out() {
+        let config: Config = Figment::new()
+            .merge(Toml::string(
+                r#"
+                ledger_default_account = "Internal"
+
+                [[ledger_accounts]]
+                tag = "acme"
+                account = "Clients:Acme"
+
+                [[ledger_accounts]]
+                message = "(?i)meeting"
+                account = "Meetings"
+                "#,
+            ))
+            .extract()
+            .unwrap();
+        let entries = vec![
+            entry("09:00", "start"),
+            entry("11:00", "fix login #Acme"),
+            entry("11:30", "team meeting"),
+            entry("12:00", "break"),
+            entry("13:00", "admin"),
+        ];
+
+        let mut out = vec![];
+        write_ledger(&mut out, &entries, &config).unwrap();
+        assert_eq!(
+            "i 2025-07-24 09:00:00 Clients:Acme  fix login #Acme\n\
+            o 2025-07-24 11:00:00\n\
+            i 2025-07-24 11:00:00 Meetings  team meeting\n\
+            o 2025-07-24 11:30:00\n\
+            i 2025-07-24 12:00:00 Internal  admin\n\
+            o 2025-07-24 13:00:00\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+}
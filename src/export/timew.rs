@@ -0,0 +1,138 @@
+use crate::config::Config;
+use crate::model::Entry;
+use crate::summary::intervals;
+use anyhow::Result;
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+const TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// An interval as written by `timew export`.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub(crate) struct TimewInterval {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub start: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+}
+
+/// Writes the intervals between the given entries in the JSON format of
+/// `timew export`, which can be read by `timew import`.
+///
+/// Each interval is tagged with the message of the entry describing it.
+/// Breaks are left out, so they become gaps between the intervals.
+pub fn write_timew<W: Write>(mut w: W, entries: &[Entry], config: &Config) -> Result<()> {
+    let mut exported = vec![];
+    for interval in intervals(entries) {
+        if interval.is_break(config)? {
+            continue;
+        }
+        exported.push(TimewInterval {
+            id: None,
+            start: format_time(interval.start),
+            end: Some(format_time(interval.end)),
+            tags: vec![interval.entry.message.clone()],
+            annotation: interval.entry.long.clone(),
+        });
+    }
+
+    // Like Timewarrior, the latest interval has the ID 1.
+    let count = exported.len() as u32;
+    for (i, interval) in exported.iter_mut().enumerate() {
+        interval.id = Some(count - i as u32);
+    }
+
+    serde_json::to_writer_pretty(&mut w, &exported)?;
+    writeln!(w)?;
+    Ok(())
+}
+
+/// Formats a local time in UTC like Timewarrior, e.g. `20250724T070000Z`.
+pub(crate) fn format_time(time: NaiveDateTime) -> String {
+    let utc = match time.and_local_timezone(Local).earliest() {
+        Some(local) => local.with_timezone(&Utc).naive_utc(),
+        None => time,
+    };
+    utc.format(TIME_FORMAT).to_string()
+}
+
+/// Parses a time in UTC like `20250724T070000Z` into local time.
+pub(crate) fn parse_time(time: &str) -> Result<NaiveDateTime> {
+    let utc = NaiveDateTime::parse_from_str(time, TIME_FORMAT)
+        .map_err(|_| anyhow::anyhow!("invalid Timewarrior time '{time}'"))?;
+    Ok(Utc
+        .from_utc_datetime(&utc)
+        .with_timezone(&Local)
+        .naive_local())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TimewInterval, format_time, parse_time, write_timew};
+    use crate::config::Config;
+    use crate::model::Entry;
+    use chrono::NaiveDateTime;
+    use figment::Figment;
+
+    fn entry(id: u32, time: &str, message: &str) -> Entry {
+        Entry {
+            id,
+            timestamp: NaiveDateTime::parse_from_str(
+                &format!("2025-07-24 {time}"),
+                "%Y-%m-%d %H:%M",
+            )
+            .unwrap(),
+            message: message.to_string(),
+            long: None,
+        }
+    }
+
+    #[test]
+    fn breaks_become_gaps() {
+        let config: Config = Figment::new().extract().unwrap();
+        let entries = vec![
+            entry(1, "09:00", "start"),
+            entry(2, "12:00", "coding"),
+            entry(3, "12:30", "break"),
+            Entry {
+                long: Some("PR #42".to_string()),
+                ..entry(4, "17:00", "review")
+            },
+        ];
+
+        let mut out = vec![];
+        write_timew(&mut out, &entries, &config).unwrap();
+        let exported: Vec<TimewInterval> = serde_json::from_slice(&out).unwrap();
+
+        let time = |t| format_time(entries[0].timestamp.date().and_time(t));
+        assert_eq!(
+            vec![
+                TimewInterval {
+                    id: Some(2),
+                    start: time("09:00:00".parse().unwrap()),
+                    end: Some(time("12:00:00".parse().unwrap())),
+                    tags: vec!["coding".to_string()],
+                    annotation: None,
+                },
+                TimewInterval {
+                    id: Some(1),
+                    start: time("12:30:00".parse().unwrap()),
+                    end: Some(time("17:00:00".parse().unwrap())),
+                    tags: vec!["review".to_string()],
+                    annotation: Some("PR #42".to_string()),
+                },
+            ],
+            exported
+        );
+        assert_eq!(
+            entries[1].timestamp,
+            parse_time(exported[0].end.as_ref().unwrap()).unwrap()
+        );
+    }
+}
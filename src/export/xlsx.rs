@@ -0,0 +1,255 @@
+use crate::config::Config;
+use crate::model::Entry;
+use crate::summary::{DaySummary, intervals};
+use anyhow::Result;
+use chrono::{Datelike, Duration, IsoWeek, NaiveDate, Weekday};
+use rust_xlsxwriter::{Color, Format, FormatBorder, Formula, Workbook, Worksheet};
+use std::io::Write;
+
+const DATE_FORMAT: &str = "yyyy-mm-dd";
+const TIME_FORMAT: &str = "hh:mm";
+const DURATION_FORMAT: &str = "[h]:mm";
+
+const HEADER_COLOR: u32 = 0xD9E1F2;
+
+struct Formats {
+    header: Format,
+    date: Format,
+    time: Format,
+    duration: Format,
+    text: Format,
+    total: Format,
+    total_date: Format,
+    total_duration: Format,
+}
+
+impl Formats {
+    fn new() -> Self {
+        let header = Format::new()
+            .set_bold()
+            .set_background_color(Color::RGB(HEADER_COLOR))
+            .set_border_bottom(FormatBorder::Thin);
+        let total = Format::new().set_bold().set_border_top(FormatBorder::Thin);
+        Self {
+            header,
+            date: Format::new().set_num_format(DATE_FORMAT),
+            time: Format::new().set_num_format(TIME_FORMAT),
+            duration: Format::new().set_num_format(DURATION_FORMAT),
+            text: Format::new().set_text_wrap(),
+            total_date: total.clone().set_num_format(DATE_FORMAT),
+            total_duration: total.clone().set_num_format(DURATION_FORMAT),
+            total,
+        }
+    }
+}
+
+/// Writes the intervals between the given entries as an Excel workbook.
+///
+/// The workbook has a summary sheet with the work time per week, followed
+/// by one sheet per month with a row per worked interval and a subtotal
+/// per day. Breaks are left out. Durations are written as Excel durations
+/// and totals as formulas, so that the sheets can be edited.
+pub fn write_xlsx<W: Write>(mut w: W, entries: &[Entry], config: &Config) -> Result<()> {
+    let formats = Formats::new();
+    let mut workbook = Workbook::new();
+
+    workbook.push_worksheet(summary_sheet(&formats, entries, config)?);
+    for month in entries.chunk_by(|a, b| {
+        (a.timestamp.year(), a.timestamp.month()) == (b.timestamp.year(), b.timestamp.month())
+    }) {
+        workbook.push_worksheet(month_sheet(&formats, month, config)?);
+    }
+
+    w.write_all(&workbook.save_to_buffer()?)?;
+    Ok(())
+}
+
+fn summary_sheet(formats: &Formats, entries: &[Entry], config: &Config) -> Result<Worksheet> {
+    let mut sheet = Worksheet::new();
+    sheet.set_name("Summary")?;
+    sheet.write_row_with_format(0, 0, ["Week", "From", "To", "Work"], &formats.header)?;
+    sheet.set_freeze_panes(1, 0)?;
+
+    let weeks = weekly_work(entries, config)?;
+    let mut row = 1;
+    for &(week, work) in &weeks {
+        let from = NaiveDate::from_isoywd_opt(week.year(), week.week(), Weekday::Mon);
+        let to = NaiveDate::from_isoywd_opt(week.year(), week.week(), Weekday::Sun);
+        sheet.write_string(row, 0, format!("{}-W{:02}", week.year(), week.week()))?;
+        if let (Some(from), Some(to)) = (from, to) {
+            sheet.write_datetime_with_format(row, 1, from, &formats.date)?;
+            sheet.write_datetime_with_format(row, 2, to, &formats.date)?;
+        }
+        sheet.write_number_with_format(row, 3, excel_duration(work), &formats.duration)?;
+        row += 1;
+    }
+
+    let total: Duration = weeks.iter().map(|&(_, work)| work).sum();
+    sheet.write_string_with_format(row, 0, "Total", &formats.total)?;
+    sheet.write_string_with_format(row, 1, "", &formats.total)?;
+    sheet.write_string_with_format(row, 2, "", &formats.total)?;
+    sheet.write_formula_with_format(
+        row,
+        3,
+        sum_formula(false, 1, row - 1, excel_duration(total)),
+        &formats.total_duration,
+    )?;
+
+    sheet.set_column_width(0, 10)?;
+    sheet.set_column_width(1, 12)?;
+    sheet.set_column_width(2, 12)?;
+    sheet.set_column_width(3, 10)?;
+    Ok(sheet)
+}
+
+fn month_sheet(formats: &Formats, entries: &[Entry], config: &Config) -> Result<Worksheet> {
+    let mut sheet = Worksheet::new();
+    sheet.set_name(entries[0].timestamp.format("%Y-%m").to_string())?;
+    sheet.write_row_with_format(
+        0,
+        0,
+        ["Date", "Start", "End", "Duration", "Message", "Long"],
+        &formats.header,
+    )?;
+    sheet.set_freeze_panes(1, 0)?;
+
+    let mut row = 1;
+    let mut total = Duration::zero();
+    for day in entries.chunk_by(|a, b| a.timestamp.date() == b.timestamp.date()) {
+        let first = row;
+        for interval in intervals(day) {
+            if interval.is_break(config)? {
+                continue;
+            }
+            let entry = interval.entry;
+            sheet.write_datetime_with_format(row, 0, interval.start.date(), &formats.date)?;
+            sheet.write_datetime_with_format(row, 1, interval.start.time(), &formats.time)?;
+            sheet.write_datetime_with_format(row, 2, interval.end.time(), &formats.time)?;
+            sheet.write_number_with_format(
+                row,
+                3,
+                excel_duration(interval.duration()),
+                &formats.duration,
+            )?;
+            sheet.write_string_with_format(row, 4, &entry.message, &formats.text)?;
+            if let Some(ref long) = entry.long {
+                sheet.write_string_with_format(row, 5, long, &formats.text)?;
+            }
+            row += 1;
+        }
+        if row == first {
+            continue;
+        }
+
+        let work = DaySummary::compute(day, config)?.work;
+        total += work;
+        sheet.write_datetime_with_format(row, 0, day[0].timestamp.date(), &formats.total_date)?;
+        sheet.write_string_with_format(row, 1, "", &formats.total)?;
+        sheet.write_string_with_format(row, 2, "", &formats.total)?;
+        sheet.write_formula_with_format(
+            row,
+            3,
+            sum_formula(true, first, row - 1, excel_duration(work)),
+            &formats.total_duration,
+        )?;
+        sheet.write_string_with_format(row, 4, "Daily total", &formats.total)?;
+        sheet.write_string_with_format(row, 5, "", &formats.total)?;
+        row += 1;
+    }
+
+    for col in [0, 1, 2, 5] {
+        sheet.write_string_with_format(row, col, "", &formats.total)?;
+    }
+    sheet.write_string_with_format(row, 4, "Monthly total", &formats.total)?;
+    sheet.write_formula_with_format(
+        row,
+        3,
+        sum_formula(true, 1, row - 1, excel_duration(total)),
+        &formats.total_duration,
+    )?;
+
+    sheet.set_column_width(0, 12)?;
+    sheet.set_column_width(1, 8)?;
+    sheet.set_column_width(2, 8)?;
+    sheet.set_column_width(3, 10)?;
+    sheet.set_column_width(4, 40)?;
+    sheet.set_column_width(5, 60)?;
+    Ok(sheet)
+}
+
+/// Returns the work time of the given entries per ISO week.
+fn weekly_work(entries: &[Entry], config: &Config) -> Result<Vec<(IsoWeek, Duration)>> {
+    let mut weeks: Vec<(IsoWeek, Duration)> = vec![];
+    for day in entries.chunk_by(|a, b| a.timestamp.date() == b.timestamp.date()) {
+        let week = day[0].timestamp.iso_week();
+        let work = DaySummary::compute(day, config)?.work;
+        match weeks.last_mut() {
+            Some((last, total)) if *last == week => *total += work,
+            _ => weeks.push((week, work)),
+        }
+    }
+    Ok(weeks)
+}
+
+/// Converts a duration into a fraction of days, as used by Excel.
+fn excel_duration(duration: Duration) -> f64 {
+    duration.num_seconds() as f64 / 86_400.0
+}
+
+/// Returns a formula summing up the duration column between the given
+/// rows, with the computed result for readers which do not recalculate.
+/// Subtotals are left out by the sums of other subtotals.
+fn sum_formula(subtotal: bool, first: u32, last: u32, result: f64) -> Formula {
+    let range = format!("D{}:D{}", first + 1, last + 1);
+    let formula = match subtotal {
+        true => format!("=SUBTOTAL(9,{range})"),
+        false => format!("=SUM({range})"),
+    };
+    Formula::new(formula).set_result(result.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{weekly_work, write_xlsx};
+    use crate::config::Config;
+    use crate::model::Entry;
+    use chrono::{Duration, NaiveDateTime};
+    use figment::Figment;
+
+    fn entry(date_time: &str, message: &str) -> Entry {
+        Entry {
+            id: 0,
+            timestamp: NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").unwrap(),
+            message: message.to_string(),
+            long: None,
+        }
+    }
+
+    #[test]
+    fn workbook() {
+        let config: Config = Figment::new().extract().unwrap();
+        let entries = vec![
+            entry("2025-07-27 09:00", "start"),
+            entry("2025-07-27 12:00", "coding"),
+            entry("2025-07-28 09:00", "start"),
+            entry("2025-07-28 10:00", "break"),
+            entry("2025-07-28 11:30", "coding"),
+            entry("2025-08-01 09:00", "start"),
+            entry("2025-08-01 11:00", "review"),
+        ];
+
+        let weeks: Vec<_> = weekly_work(&entries, &config)
+            .unwrap()
+            .into_iter()
+            .map(|(week, work)| (week.week(), work))
+            .collect();
+        assert_eq!(
+            vec![(30, Duration::hours(3)), (31, Duration::minutes(210))],
+            weeks
+        );
+
+        let mut out = vec![];
+        write_xlsx(&mut out, &entries, &config).unwrap();
+        assert!(out.starts_with(b"PK"));
+    }
+}
@@ -0,0 +1,17 @@
+//! Formatting shared by the timesheets, invoices and reports.
+
+use chrono::Duration;
+
+/// Formats a duration in hours and minutes, e.g. `7:05`.
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Escapes the given text for HTML.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
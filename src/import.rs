@@ -0,0 +1,7 @@
+//! Conversion of data from other tools into tracked entries.
+
+mod ics;
+mod timew;
+
+pub use ics::{entries_for_events, read_ics};
+pub use timew::read_timew;
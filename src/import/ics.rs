@@ -0,0 +1,412 @@
+use crate::model::{Entry, NewEntry};
+use anyhow::Result;
+use chrono::{Duration, NaiveDateTime, TimeZone, Utc};
+
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// A timed event of a calendar.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Event {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub summary: String,
+    pub description: Option<String>,
+}
+
+#[derive(Default)]
+struct PartialEvent {
+    start: Option<NaiveDateTime>,
+    end: Option<NaiveDateTime>,
+    duration: Option<Duration>,
+    summary: Option<String>,
+    description: Option<String>,
+    skip: bool,
+}
+
+/// Reads all timed events from an iCalendar file, ordered by their start.
+///
+/// Times in UTC are converted to local time and floating times are taken as
+/// local time. Times in other zones, given by a `TZID` parameter, are only
+/// accepted if the zone is the local one. All-day and cancelled events are
+/// skipped.
+pub fn read_ics(content: &str) -> Result<Vec<Event>> {
+    read_ics_in(content, local_time_zone().as_deref())
+}
+
+/// Reads all timed events, taking the given zone as the local one.
+fn read_ics_in(content: &str, local_zone: Option<&str>) -> Result<Vec<Event>> {
+    let mut events = vec![];
+    let mut event: Option<PartialEvent> = None;
+    // Components nested in events, like alarms, are ignored.
+    let mut nested = 0;
+
+    for line in unfold(content) {
+        let Some((name, params, value)) = split_line(&line) else {
+            continue;
+        };
+
+        match (name.as_str(), value) {
+            ("BEGIN", "VEVENT") if event.is_none() => event = Some(PartialEvent::default()),
+            ("BEGIN", _) if event.is_some() => nested += 1,
+            ("END", "VEVENT") if nested == 0 => {
+                if let Some(event) = event.take() {
+                    events.extend(event.finish()?);
+                }
+            }
+            ("END", _) if event.is_some() => nested -= 1,
+            _ => {
+                let Some(event) = event.as_mut().filter(|_| nested == 0) else {
+                    continue;
+                };
+                match name.as_str() {
+                    "DTSTART" | "DTEND" if params.contains("VALUE=DATE") => event.skip = true,
+                    "DTSTART" => event.start = Some(parse_date_time(value, params, local_zone)?),
+                    "DTEND" => event.end = Some(parse_date_time(value, params, local_zone)?),
+                    "DURATION" => event.duration = Some(parse_duration(value)?),
+                    "SUMMARY" => event.summary = Some(unescape(value)),
+                    "DESCRIPTION" => event.description = Some(unescape(value)),
+                    "STATUS" if value.eq_ignore_ascii_case("CANCELLED") => event.skip = true,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    events.sort_by_key(|e| e.start);
+    Ok(events)
+}
+
+impl PartialEvent {
+    fn finish(self) -> Result<Option<Event>> {
+        if self.skip {
+            return Ok(None);
+        }
+        let start = self
+            .start
+            .ok_or_else(|| anyhow::anyhow!("event without start"))?;
+        let end = match (self.end, self.duration) {
+            (Some(end), _) => end,
+            (None, Some(duration)) => start + duration,
+            (None, None) => return Ok(None),
+        };
+        Ok(Some(Event {
+            start,
+            end,
+            summary: self.summary.unwrap_or_default(),
+            description: self.description.filter(|d| !d.trim().is_empty()),
+        }))
+    }
+}
+
+/// Proposes the entries to track the given events.
+///
+/// As an entry describes the time since the previous one, each event gets
+/// an entry at its end with its summary and description. The task the
+/// event interrupted ends with an entry at the event start, which repeats
+/// the entry following it. Without following entries, the previous entry
+/// of the day is repeated, and on days without entries, `start` is used.
+/// Entries which already exist are not proposed again.
+pub fn entries_for_events(events: &[Event], existing: &[Entry]) -> Vec<NewEntry> {
+    let mut known: Vec<NewEntry> = existing
+        .iter()
+        .map(|e| NewEntry {
+            timestamp: e.timestamp,
+            message: e.message.clone(),
+            long: e.long.clone(),
+        })
+        .collect();
+    known.sort_by_key(|e| e.timestamp);
+
+    let mut proposed = vec![];
+    for event in events.iter().filter(|e| e.start < e.end) {
+        let first = proposed.len();
+        if !known.iter().any(|e| e.timestamp == event.start) {
+            let same_day = |e: &&NewEntry| e.timestamp.date() == event.start.date();
+            let interrupted = known
+                .iter()
+                .filter(same_day)
+                .find(|e| e.timestamp > event.start)
+                .or_else(|| {
+                    known
+                        .iter()
+                        .filter(same_day)
+                        .rfind(|e| e.timestamp < event.start)
+                });
+            let resume = match interrupted {
+                Some(e) => NewEntry {
+                    timestamp: event.start,
+                    ..e.clone()
+                },
+                None => NewEntry {
+                    timestamp: event.start,
+                    message: "start".to_string(),
+                    long: None,
+                },
+            };
+            proposed.push(resume);
+        }
+
+        let entry = NewEntry {
+            timestamp: event.end,
+            message: event.summary.clone(),
+            long: event.description.clone(),
+        };
+        if !known
+            .iter()
+            .any(|e| e.timestamp == entry.timestamp && e.message == entry.message)
+        {
+            proposed.push(entry);
+        }
+
+        known.extend(proposed[first..].iter().cloned());
+        known.sort_by_key(|e| e.timestamp);
+    }
+
+    proposed
+}
+
+/// Joins lines continued by a leading space or tab.
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Splits a content line into its upper case name, its parameters and its
+/// value.
+fn split_line(line: &str) -> Option<(String, &str, &str)> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let (name, params) = head.split_once(';').unwrap_or((head, ""));
+    Some((name.to_ascii_uppercase(), params, value))
+}
+
+/// Returns the IANA name of the local time zone, e.g. `Europe/Berlin`.
+fn local_time_zone() -> Option<String> {
+    match std::env::var("TZ") {
+        Ok(tz) if !tz.is_empty() => Some(tz.trim_start_matches(':').to_string()),
+        _ => iana_time_zone::get_timezone().ok(),
+    }
+}
+
+/// Parses a date and time in local time, converting it from UTC if needed.
+/// Other time zones can not be converted and are rejected.
+fn parse_date_time(value: &str, params: &str, local_zone: Option<&str>) -> Result<NaiveDateTime> {
+    let invalid = || anyhow::anyhow!("invalid date and time '{value}'");
+    let tzid = params
+        .split(';')
+        .find_map(|p| p.strip_prefix("TZID="))
+        .map(|tz| tz.trim_matches('"'));
+    let (value, utc) = match (value.strip_suffix('Z'), tzid) {
+        (Some(utc), _) => (utc, true),
+        (None, Some("UTC" | "Etc/UTC" | "GMT" | "Etc/GMT")) => (value, true),
+        (None, Some(tz)) if Some(tz) != local_zone => {
+            return Err(anyhow::anyhow!(
+                "can not convert '{value}' from the time zone {tz} to the local time \
+                zone {}; only UTC and local times are supported",
+                local_zone.unwrap_or("(unknown)")
+            ));
+        }
+        (None, _) => (value, false),
+    };
+
+    let time = NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT).map_err(|_| invalid())?;
+    Ok(match utc {
+        true => Utc
+            .from_utc_datetime(&time)
+            .with_timezone(&chrono::Local)
+            .naive_local(),
+        false => time,
+    })
+}
+
+/// Parses a duration like `PT1H30M` or `P1D`.
+fn parse_duration(value: &str) -> Result<Duration> {
+    let invalid = || anyhow::anyhow!("invalid duration '{value}'");
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut rest = value.strip_prefix('P').ok_or_else(invalid)?;
+    let mut duration = Duration::zero();
+    let mut time = false;
+    while !rest.is_empty() {
+        if let Some(r) = rest.strip_prefix('T') {
+            time = true;
+            rest = r;
+            continue;
+        }
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let n: i64 = rest[..digits].parse().map_err(|_| invalid())?;
+        let designator = rest[digits..].chars().next().ok_or_else(invalid)?;
+        duration += match (time, designator) {
+            (false, 'W') => Duration::weeks(n),
+            (false, 'D') => Duration::days(n),
+            (true, 'H') => Duration::hours(n),
+            (true, 'M') => Duration::minutes(n),
+            (true, 'S') => Duration::seconds(n),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "unknown designator '{designator}' in duration '{value}'"
+                ));
+            }
+        };
+        rest = &rest[digits + designator.len_utf8()..];
+    }
+    Ok(if negative { -duration } else { duration })
+}
+
+/// Reverts the escaping of text values as defined in RFC 5545, section
+/// 3.3.11.
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n' | 'N') => out.push('\n'),
+                Some(c) => out.push(c),
+                None => out.push('\\'),
+            },
+            (c, false) => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Event, entries_for_events, parse_date_time, parse_duration, read_ics_in};
+    use crate::model::Entry;
+    use chrono::{Duration, NaiveDateTime};
+
+    fn time(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("2025-07-24 {s}"), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn entry(t: &str, message: &str) -> Entry {
+        Entry {
+            id: 0,
+            timestamp: time(t),
+            message: message.to_string(),
+            long: None,
+        }
+    }
+
+    fn event(start: &str, end: &str, summary: &str) -> Event {
+        Event {
+            start: time(start),
+            end: time(end),
+            summary: summary.to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn read_events() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART;TZID=Europe/Berlin:20250724T140000\r\n\
+            DURATION:PT1H30M\r\n\
+            SUMMARY:Planning\\, Q3\r\n\
+            BEGIN:VALARM\r\n\
+            DESCRIPTION:Reminder\r\n\
+            END:VALARM\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART:20250724T100000\r\n\
+            DTEND:20250724T103000\r\n\
+            SUMMARY:Daily\r\n\
+            DESCRIPTION:Agenda:\\n- updates\r\n\
+            \x20 and blockers\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART;VALUE=DATE:20250724\r\n\
+            SUMMARY:Holiday\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART:20250724T160000\r\n\
+            DTEND:20250724T170000\r\n\
+            STATUS:CANCELLED\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = read_ics_in(ics, Some("Europe/Berlin")).unwrap();
+        assert_eq!(
+            vec![
+                Event {
+                    description: Some("Agenda:\n- updates and blockers".to_string()),
+                    ..event("10:00", "10:30", "Daily")
+                },
+                event("14:00", "15:30", "Planning, Q3"),
+            ],
+            events
+        );
+
+        let err = read_ics_in(ics, Some("America/New_York")).unwrap_err();
+        assert!(err.to_string().contains("time zone Europe/Berlin"), "{err}");
+        assert_eq!(
+            parse_date_time("20250724T140000Z", "", None).unwrap(),
+            parse_date_time("20250724T140000", "TZID=\"UTC\"", None).unwrap()
+        );
+
+        assert_eq!(Duration::minutes(90), parse_duration("PT1H30M").unwrap());
+        assert_eq!(Duration::days(-8), parse_duration("-P1W1D").unwrap());
+        assert!(parse_duration("1H").is_err());
+        let err = parse_duration("PT1é").unwrap_err();
+        assert_eq!("unknown designator 'é' in duration 'PT1é'", err.to_string());
+        assert!(parse_duration("P1H").is_err());
+    }
+
+    #[test]
+    fn propose_entries() {
+        let existing = vec![entry("09:00", "start"), entry("12:00", "coding")];
+        let events = vec![
+            event("10:00", "10:30", "Daily"),
+            event("10:30", "11:00", "Review"),
+            event("13:00", "14:00", "Planning"),
+        ];
+
+        let proposed: Vec<_> = entries_for_events(&events, &existing)
+            .into_iter()
+            .map(|e| (e.timestamp.format("%H:%M").to_string(), e.message))
+            .collect();
+        let expected = [
+            ("10:00", "coding"),
+            ("10:30", "Daily"),
+            ("11:00", "Review"),
+            ("13:00", "coding"),
+            ("14:00", "Planning"),
+        ];
+        assert_eq!(
+            expected
+                .iter()
+                .map(|(t, m)| (t.to_string(), m.to_string()))
+                .collect::<Vec<_>>(),
+            proposed
+        );
+
+        // Importing again proposes nothing new.
+        let mut all = existing.clone();
+        all.extend(proposed.iter().map(|(t, m)| entry(t, m)));
+        assert!(entries_for_events(&events, &all).is_empty());
+
+        let first = entries_for_events(&events[..1], &[]);
+        assert_eq!("start", first[0].message);
+    }
+}
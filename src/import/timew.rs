@@ -0,0 +1,132 @@
+use crate::config::Config;
+use crate::export::timew::{TimewInterval, parse_time};
+use crate::model::NewEntry;
+use anyhow::Result;
+use regex::Regex;
+
+/// Reads the JSON written by `timew export` and returns the entries to
+/// track its intervals.
+///
+/// Each interval gets an entry at its end with its tags as message, or
+/// `untagged`, and its annotation as long text. Intervals not directly
+/// following another one on the same day get an entry at their start as
+/// well, which is `start` for the first interval of a day and `break` for
+/// gaps. Fails if these messages do not match the configured `start_regex`
+/// and `break_regex`. Open intervals are skipped.
+pub fn read_timew(content: &str, config: &Config) -> Result<Vec<NewEntry>> {
+    let start_message = marker("start", &config.start_regex, "start_regex")?;
+    let break_message = marker("break", &config.break_regex, "break_regex")?;
+
+    let intervals: Vec<TimewInterval> = serde_json::from_str(content)?;
+
+    let mut parsed = vec![];
+    for interval in intervals {
+        let Some(ref end) = interval.end else {
+            continue;
+        };
+        parsed.push((parse_time(&interval.start)?, parse_time(end)?, interval));
+    }
+    parsed.sort_by_key(|(start, _, _)| *start);
+
+    let mut entries: Vec<NewEntry> = vec![];
+    for (start, end, interval) in parsed {
+        let previous = entries.last().map(|e| e.timestamp);
+        if previous != Some(start) {
+            let message = match previous {
+                Some(previous) if previous.date() == start.date() => break_message,
+                _ => start_message,
+            };
+            entries.push(NewEntry {
+                timestamp: start,
+                message: message.to_string(),
+                long: None,
+            });
+        }
+        let message = match interval.tags.is_empty() {
+            true => "untagged".to_string(),
+            false => interval.tags.join(" "),
+        };
+        entries.push(NewEntry {
+            timestamp: end,
+            message,
+            long: interval.annotation,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Returns the given message if it matches the configured regex, so that
+/// the entries to import are counted as intended.
+fn marker<'a>(message: &'a str, regex: &str, key: &str) -> Result<&'a str> {
+    match Regex::new(regex)?.is_match(message) {
+        true => Ok(message),
+        false => Err(anyhow::anyhow!(
+            "can not import gaps between intervals as '{message}' does not match the configured {key}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::read_timew;
+    use crate::config::Config;
+    use crate::export::timew::format_time;
+    use chrono::NaiveDateTime;
+    use figment::Figment;
+    use figment::providers::{Format, Toml};
+
+    fn config(toml: &str) -> Config {
+        Figment::new().merge(Toml::string(toml)).extract().unwrap()
+    }
+
+    fn time(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn intervals_to_entries() {
+        let t = |s| format_time(time(s));
+        let json = format!(
+            r#"[
+                {{"id":4,"start":"{}","end":"{}","tags":["coding","tracker"]}},
+                {{"id":3,"start":"{}","end":"{}","tags":["review"],"annotation":"PR #42"}},
+                {{"id":2,"start":"{}","end":"{}"}},
+                {{"id":1,"start":"{}"}}
+            ]"#,
+            t("2025-07-24 09:00"),
+            t("2025-07-24 12:00"),
+            t("2025-07-24 12:30"),
+            t("2025-07-24 17:00"),
+            t("2025-07-25 09:00"),
+            t("2025-07-25 10:00"),
+            t("2025-07-25 10:00"),
+        );
+
+        let entries = read_timew(&json, &config("")).unwrap();
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.timestamp.format("%d %H:%M").to_string(),
+                    e.message.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("24 09:00".to_string(), "start"),
+                ("24 12:00".to_string(), "coding tracker"),
+                ("24 12:30".to_string(), "break"),
+                ("24 17:00".to_string(), "review"),
+                ("25 09:00".to_string(), "start"),
+                ("25 10:00".to_string(), "untagged"),
+            ],
+            summary
+        );
+        assert_eq!(Some("PR #42".to_string()), entries[3].long);
+
+        let err = read_timew(&json, &config("break_regex = \"^lunch$\"")).unwrap_err();
+        assert!(err.to_string().contains("break_regex"), "{err}");
+    }
+}
@@ -0,0 +1,562 @@
+//! Invoices over the billable time of a client.
+
+use crate::billing::{Billing, amount, format_amount, rate_for};
+use crate::clock;
+use crate::config::{Client, Config};
+use crate::format::{escape, format_duration};
+use crate::model::Entry;
+use crate::rounding::billed_durations;
+use crate::summary::intervals;
+use crate::util::DateRange;
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+mod pdf;
+
+/// How the billed intervals are combined into line items.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Grouping {
+    /// One item per day, listing the tasks of the day.
+    Day,
+    /// One item per distinct message over the whole period.
+    Task,
+}
+
+/// A line item of an invoice billed at an hourly rate. The amount is given
+/// in cents.
+#[derive(Clone, PartialEq, Debug)]
+pub struct InvoiceItem {
+    pub date: Option<NaiveDate>,
+    pub description: String,
+    pub duration: Duration,
+    pub rate: f64,
+    pub amount: i64,
+}
+
+/// The billable time of a client within a period.
+#[derive(Clone, Debug)]
+pub struct Invoice {
+    /// The invoice number, which is only assigned once the invoice is
+    /// recorded. Invoices without one are drafts.
+    pub number: Option<String>,
+    pub issued: NaiveDate,
+    pub range: DateRange,
+    pub client: Client,
+    pub issuer: Option<String>,
+    pub items: Vec<InvoiceItem>,
+    /// IDs of the entries describing the billed intervals.
+    pub entry_ids: Vec<u32>,
+}
+
+impl Invoice {
+    /// Collects the billable intervals of the given entries which match the
+    /// filter of the client and have not been invoiced yet. Breaks are never
+    /// billed.
+    ///
+    /// The intervals are rounded by the policy of the client before they are
+    /// added to their line items. Intervals of projects with their own rate
+    /// are billed in separate items at that rate.
+    pub fn new(
+        client: &Client,
+        range: DateRange,
+        entries: &[Entry],
+        invoiced: &HashSet<u32>,
+        grouping: Grouping,
+        config: &Config,
+    ) -> Result<Self> {
+        let mut billed = vec![];
+        for interval in intervals(entries) {
+            let entry = interval.entry;
+            if range.contains(interval.start.date())
+                && !invoiced.contains(&entry.id)
+                && !interval.is_break(config)?
+                && entry.matches(&client.filter)?
+                && Billing::of(entry, config)?.billable
+            {
+                billed.push(interval);
+            }
+        }
+        let durations = billed_durations(config.rounding_for(Some(client)).as_ref(), &billed);
+
+        let mut items: Vec<InvoiceItem> = vec![];
+        let mut entry_ids = vec![];
+        for (interval, duration) in billed.iter().zip(durations) {
+            let entry = interval.entry;
+            entry_ids.push(entry.id);
+            let rate = rate_for(entry, config, client)?;
+
+            let (date, key) = match grouping {
+                Grouping::Day => (Some(interval.start.date()), None),
+                Grouping::Task => (None, Some(&entry.message)),
+            };
+            let item = items.iter_mut().find(|i| {
+                i.date == date && i.rate == rate && (key.is_none() || Some(&i.description) == key)
+            });
+            match item {
+                Some(item) => {
+                    item.duration += duration;
+                    if grouping == Grouping::Day
+                        && !item.description.split(", ").any(|d| d == entry.message)
+                    {
+                        item.description += ", ";
+                        item.description += &entry.message;
+                    }
+                }
+                None => items.push(InvoiceItem {
+                    date,
+                    description: entry.message.clone(),
+                    duration,
+                    rate,
+                    amount: 0,
+                }),
+            }
+        }
+
+        if items.is_empty() {
+            return Err(anyhow::anyhow!(
+                "no uninvoiced entries of {} in {range}",
+                client.name
+            ));
+        }
+        for item in &mut items {
+            item.amount = amount(item.duration, item.rate);
+        }
+
+        Ok(Self {
+            number: None,
+            issued: clock::today(),
+            range,
+            client: client.clone(),
+            issuer: config.invoice_issuer.clone(),
+            items,
+            entry_ids,
+        })
+    }
+
+    /// Returns the billed time of all items.
+    pub fn duration(&self) -> Duration {
+        self.items.iter().map(|i| i.duration).sum()
+    }
+
+    /// Returns the amount of all items in cents.
+    pub fn total(&self) -> i64 {
+        self.items.iter().map(|i| i.amount).sum()
+    }
+
+    /// Returns the title of the invoice, e.g. `Invoice 2025-0001`.
+    pub fn title(&self) -> String {
+        match self.number {
+            Some(ref number) => format!("Invoice {number}"),
+            None => "Invoice (draft)".to_string(),
+        }
+    }
+
+    /// Formats an amount in cents with the currency of the client.
+    pub fn format_amount(&self, cents: i64) -> String {
+        format_amount(cents, &self.client.currency)
+    }
+
+    /// Renders the invoice as a self-contained HTML document.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let title = escape(&self.title());
+        // Writing to a string can not fail.
+        let _ = write!(
+            html,
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+@page {{ size: A4; margin: 20mm; }}
+body {{ font-family: Helvetica, Arial, sans-serif; font-size: 10pt; color: #222; max-width: 180mm; margin: 0 auto; }}
+h1 {{ font-size: 16pt; margin-bottom: 4pt; }}
+.parties {{ display: flex; gap: 24pt; margin-bottom: 12pt; }}
+.parties div {{ white-space: pre-line; }}
+.parties div span {{ display: block; color: #666; font-size: 8pt; text-transform: uppercase; }}
+table {{ width: 100%; border-collapse: collapse; }}
+th, td {{ padding: 3pt 6pt; border-bottom: 1px solid #ddd; text-align: left; }}
+th {{ background: #d9e1f2; border-bottom: 1px solid #888; }}
+.number {{ text-align: right; font-variant-numeric: tabular-nums; }}
+tfoot td {{ font-weight: bold; border-top: 2px solid #888; border-bottom: none; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<div class="parties">
+<div><span>From</span>{}</div>
+<div><span>To</span>{}</div>
+<div><span>Details</span>Date: {}
+Period: {}
+Rate: {} per hour</div>
+</div>
+<table>
+<thead>
+<tr><th>Date</th><th>Description</th><th class="number">Hours</th><th class="number">Amount</th></tr>
+</thead>
+<tbody>
+"#,
+            escape(self.issuer.as_deref().unwrap_or_default()),
+            escape(&self.recipient()),
+            self.issued,
+            self.range,
+            escape(&self.format_rate()),
+        );
+
+        for item in &self.items {
+            let _ = writeln!(
+                html,
+                r#"<tr><td>{}</td><td>{}</td><td class="number">{}</td><td class="number">{}</td></tr>"#,
+                item.date.map(|d| d.to_string()).unwrap_or_default(),
+                escape(&self.describe(item)),
+                format_duration(item.duration),
+                escape(&self.format_amount(item.amount)),
+            );
+        }
+
+        let _ = write!(
+            html,
+            r#"</tbody>
+<tfoot>
+<tr><td colspan="2">Total</td><td class="number">{}</td><td class="number">{}</td></tr>
+</tfoot>
+</table>
+</body>
+</html>
+"#,
+            format_duration(self.duration()),
+            escape(&self.format_amount(self.total())),
+        );
+        html
+    }
+
+    /// Renders the invoice as JSON for further processing, e.g. by
+    /// accounting software. Amounts are given in cents.
+    pub fn to_json(&self) -> String {
+        let items: Vec<_> = self
+            .items
+            .iter()
+            .map(|i| {
+                serde_json::json!({
+                    "date": i.date.map(|d| d.to_string()),
+                    "description": i.description,
+                    "minutes": i.duration.num_minutes(),
+                    "rate": i.rate,
+                    "amount": i.amount,
+                })
+            })
+            .collect();
+        let json = serde_json::json!({
+            "number": self.number,
+            "issued": self.issued.to_string(),
+            "start": self.range.start.to_string(),
+            "end": self.range.end.to_string(),
+            "issuer": self.issuer,
+            "client": {
+                "name": self.client.name,
+                "address": self.client.address,
+            },
+            "currency": self.client.currency,
+            "rate": self.client.rate,
+            "items": items,
+            "minutes": self.duration().num_minutes(),
+            "total": self.total(),
+        });
+        // Serializing a JSON value can not fail.
+        serde_json::to_string_pretty(&json).unwrap_or_default() + "\n"
+    }
+
+    /// Renders the invoice as a PDF document, which continues on further
+    /// pages if the items do not fit on one.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        pdf::render(self)
+    }
+
+    /// Returns the description of the item, naming its rate if it differs
+    /// from the one of the client.
+    fn describe(&self, item: &InvoiceItem) -> String {
+        match item.rate == self.client.rate {
+            true => item.description.clone(),
+            false => format!(
+                "{} ({:.2} {} per hour)",
+                item.description, item.rate, self.client.currency
+            ),
+        }
+    }
+
+    fn recipient(&self) -> String {
+        match self.client.address {
+            Some(ref address) => format!("{}\n{address}", self.client.name),
+            None => self.client.name.clone(),
+        }
+    }
+
+    fn format_rate(&self) -> String {
+        format!("{:.2} {}", self.client.rate, self.client.currency)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Grouping, Invoice};
+    use crate::config::Config;
+    use crate::model::Entry;
+    use crate::util::DateRange;
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use figment::Figment;
+    use figment::providers::{Format, Toml};
+    use std::collections::HashSet;
+
+    fn entry(id: u32, date_time: &str, message: &str) -> Entry {
+        Entry {
+            id,
+            timestamp: NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").unwrap(),
+            message: message.to_string(),
+            long: None,
+        }
+    }
+
+    fn config() -> Config {
+        Figment::new()
+            .merge(Toml::string(
+                r#"
+                invoice_issuer = "Jo Doe"
+
+                [[clients]]
+                name = "Acme"
+                tag = "acme"
+                rate = 80
+                increment = "15m"
+                address = "1 Road <Town>"
+                "#,
+            ))
+            .extract()
+            .unwrap()
+    }
+
+    fn entries() -> Vec<Entry> {
+        vec![
+            entry(1, "2025-07-01 09:00", "start"),
+            entry(2, "2025-07-01 10:10", "login #acme"),
+            entry(3, "2025-07-01 10:30", "mails"),
+            entry(4, "2025-07-01 11:00", "review #acme"),
+            entry(5, "2025-07-01 11:30", "break"),
+            entry(6, "2025-07-01 12:00", "login #acme"),
+            entry(7, "2025-07-02 09:00", "start"),
+            entry(8, "2025-07-02 10:00", "login #acme"),
+        ]
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn items_by_day_and_task() {
+        let config = config();
+        let client = config.client("ACME").unwrap();
+        let range = DateRange::new(date("2025-07-01"), date("2025-07-31")).unwrap();
+        let entries = entries();
+
+        let invoice = Invoice::new(
+            client,
+            range,
+            &entries,
+            &HashSet::new(),
+            Grouping::Day,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(vec![2, 4, 6, 8], invoice.entry_ids);
+        assert_eq!(2, invoice.items.len());
+        let first = &invoice.items[0];
+        assert_eq!(Some(date("2025-07-01")), first.date);
+        assert_eq!("login #acme, review #acme", first.description);
+        // 1:10 is rounded to 1:15, 0:30 stays and 0:30 after the break stays.
+        assert_eq!(Duration::minutes(135), first.duration);
+        assert_eq!(18000, first.amount);
+        assert_eq!(26000, invoice.total());
+        assert_eq!("260.00 EUR", invoice.format_amount(invoice.total()));
+
+        let invoice = Invoice::new(
+            client,
+            range,
+            &entries,
+            &HashSet::new(),
+            Grouping::Task,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(2, invoice.items.len());
+        assert_eq!(None, invoice.items[0].date);
+        assert_eq!("login #acme", invoice.items[0].description);
+        assert_eq!(Duration::minutes(165), invoice.items[0].duration);
+        assert_eq!(Duration::minutes(30), invoice.items[1].duration);
+
+        let invoiced = HashSet::from([2, 4, 6]);
+        let invoice =
+            Invoice::new(client, range, &entries, &invoiced, Grouping::Day, &config).unwrap();
+        assert_eq!(vec![8], invoice.entry_ids);
+
+        let invoiced = HashSet::from([2, 4, 6, 8]);
+        assert!(Invoice::new(client, range, &entries, &invoiced, Grouping::Day, &config).is_err());
+    }
+
+    #[test]
+    fn client_rounding() {
+        let config: Config = Figment::new()
+            .merge(Toml::string(
+                r#"
+                rounding = { step = "5m" }
+
+                [[clients]]
+                name = "Acme"
+                tag = "acme"
+                rate = 60
+                rounding = { mode = "down", step = "1h", per = "day", minimum = "2h" }
+
+                [[clients]]
+                name = "Other"
+                tag = "other"
+                rate = 60
+                "#,
+            ))
+            .extract()
+            .unwrap();
+        let range = DateRange::new(date("2025-07-01"), date("2025-07-31")).unwrap();
+        let client = config.client("acme").unwrap();
+        let invoice = Invoice::new(
+            client,
+            range,
+            &entries(),
+            &HashSet::new(),
+            Grouping::Day,
+            &config,
+        )
+        .unwrap();
+        // 2:10 on the first day is rounded down, 1:00 on the second raised.
+        assert_eq!(Duration::minutes(120), invoice.items[0].duration);
+        assert_eq!(Duration::minutes(120), invoice.items[1].duration);
+        assert_eq!(24000, invoice.total());
+
+        let other = config.client("other").unwrap();
+        let rounding = config.rounding_for(Some(other)).unwrap();
+        assert_eq!(Duration::minutes(5), rounding.round(Duration::minutes(3)));
+    }
+
+    #[test]
+    fn project_rates() {
+        let config: Config = Figment::new()
+            .merge(Toml::string(
+                r#"
+                [[clients]]
+                name = "Acme"
+                tag = "acme"
+                rate = 80
+
+                [[projects]]
+                name = "Review"
+                message = "^review"
+                rate = 120
+                "#,
+            ))
+            .extract()
+            .unwrap();
+        let client = config.client("acme").unwrap();
+        let range = DateRange::new(date("2025-07-01"), date("2025-07-31")).unwrap();
+        let invoice = Invoice::new(
+            client,
+            range,
+            &entries(),
+            &HashSet::new(),
+            Grouping::Day,
+            &config,
+        )
+        .unwrap();
+
+        // The review is billed in its own item at the project rate.
+        assert_eq!(3, invoice.items.len());
+        let review = &invoice.items[1];
+        assert_eq!("review #acme", review.description);
+        assert_eq!(Duration::minutes(30), review.duration);
+        assert_eq!(6000, review.amount);
+        // 1:10 and 0:30 of logins on the first day at the client rate.
+        assert_eq!(13333, invoice.items[0].amount);
+        assert_eq!(13333 + 6000 + 8000, invoice.total());
+        assert!(
+            invoice
+                .to_html()
+                .contains("review #acme (120.00 EUR per hour)")
+        );
+    }
+
+    #[test]
+    fn rendering() {
+        let config = config();
+        let client = config.client("acme").unwrap();
+        let range = DateRange::new(date("2025-07-01"), date("2025-07-31")).unwrap();
+        let mut invoice = Invoice::new(
+            client,
+            range,
+            &entries(),
+            &HashSet::new(),
+            Grouping::Day,
+            &config,
+        )
+        .unwrap();
+        assert_eq!("Invoice (draft)", invoice.title());
+        invoice.number = Some("2025-0001".to_string());
+
+        let html = invoice.to_html();
+        assert!(html.contains("<h1>Invoice 2025-0001</h1>"));
+        assert!(html.contains("Acme\n1 Road &lt;Town&gt;"));
+        assert!(html.contains(
+            "<td>2025-07-02</td><td>login #acme</td>\
+            <td class=\"number\">1:00</td><td class=\"number\">80.00 EUR</td>"
+        ));
+
+        let json: serde_json::Value = serde_json::from_str(&invoice.to_json()).unwrap();
+        assert_eq!("2025-0001", json["number"]);
+        assert_eq!(26000, json["total"]);
+        assert_eq!(195, json["minutes"]);
+        assert_eq!("2025-07-01", json["items"][0]["date"]);
+
+        assert!(invoice.to_pdf().starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn pdf_continues_on_further_pages() {
+        let config = config();
+        let client = config.client("acme").unwrap();
+        let range = DateRange::new(date("2025-07-01"), date("2025-07-31")).unwrap();
+        // Each task is an item of its own.
+        let entries: Vec<_> = (1..=31)
+            .flat_map(|day| {
+                let at = |time| format!("2025-07-{day:02} {time}");
+                [
+                    entry(day * 3, &at("09:00"), "start"),
+                    entry(day * 3 + 1, &at("10:00"), &format!("task {day} #acme")),
+                    entry(day * 3 + 2, &at("11:00"), &format!("review {day} #acme")),
+                ]
+            })
+            .collect();
+        let invoice = Invoice::new(
+            client,
+            range,
+            &entries,
+            &HashSet::new(),
+            Grouping::Task,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(62, invoice.items.len());
+
+        let pdf = invoice.to_pdf();
+        let pages = pdf.windows(12).filter(|w| w == b"/Type /Page\n").count();
+        assert_eq!(2, pages);
+        assert!(pdf.windows(8).any(|w| w == b"/Count 2"));
+    }
+}
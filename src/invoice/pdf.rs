@@ -0,0 +1,124 @@
+use super::Invoice;
+use crate::format::format_duration;
+use crate::pdf::{
+    BOLD, MARGIN, PAGE_HEIGHT, PAGE_WIDTH, REGULAR, document, line, text, text_right,
+};
+use pdf_writer::Content;
+
+const ROW_HEIGHT: f32 = 16.0;
+
+/// X positions of the date and description columns and right edges of the
+/// hours and amount columns.
+const COLUMNS: [f32; 4] = [MARGIN, 130.0, 440.0, PAGE_WIDTH - MARGIN];
+
+/// Longest description in characters which fits its column.
+const MAX_DESCRIPTION: usize = 52;
+
+/// Renders the invoice on A4 pages. Items which do not fit on the first
+/// page are continued on further pages below a repeated table header.
+pub(super) fn render(invoice: &Invoice) -> Vec<u8> {
+    let mut pages = vec![];
+    let mut content = Content::new();
+    let mut y = PAGE_HEIGHT - MARGIN;
+
+    text(&mut content, BOLD, 16.0, MARGIN, y, &invoice.title());
+    y -= 28.0;
+
+    let details = format!(
+        "Date: {}\nPeriod: {}\nRate: {} per hour",
+        invoice.issued,
+        invoice.range,
+        invoice.format_rate()
+    );
+    let parties = [
+        (MARGIN, "FROM", invoice.issuer.clone().unwrap_or_default()),
+        (230.0, "TO", invoice.recipient()),
+        (400.0, "DETAILS", details),
+    ];
+    let mut lowest = y;
+    for (x, label, lines) in parties {
+        text(&mut content, REGULAR, 7.0, x, y, label);
+        let mut line_y = y - 12.0;
+        for l in lines.lines() {
+            text(&mut content, REGULAR, 10.0, x, line_y, l);
+            line_y -= 12.0;
+        }
+        lowest = lowest.min(line_y);
+    }
+    y = table_header(&mut content, lowest - 24.0);
+
+    for item in &invoice.items {
+        if y < MARGIN {
+            pages.push(std::mem::replace(&mut content, Content::new()));
+            y = table_header(&mut content, PAGE_HEIGHT - MARGIN);
+        }
+        let date = item.date.map(|d| d.to_string()).unwrap_or_default();
+        text(&mut content, REGULAR, 10.0, COLUMNS[0], y, &date);
+        text(
+            &mut content,
+            REGULAR,
+            10.0,
+            COLUMNS[1],
+            y,
+            &truncate(&invoice.describe(item)),
+        );
+        text_right(
+            &mut content,
+            REGULAR,
+            10.0,
+            COLUMNS[2],
+            y,
+            &format_duration(item.duration),
+        );
+        text_right(
+            &mut content,
+            REGULAR,
+            10.0,
+            COLUMNS[3],
+            y,
+            &invoice.format_amount(item.amount),
+        );
+        y -= ROW_HEIGHT;
+    }
+    if y < MARGIN {
+        pages.push(std::mem::replace(&mut content, Content::new()));
+        y = table_header(&mut content, PAGE_HEIGHT - MARGIN);
+    }
+
+    line(&mut content, y + ROW_HEIGHT - 5.0, 1.2);
+    let total = invoice.format_amount(invoice.total());
+    text(&mut content, BOLD, 10.0, COLUMNS[0], y, "Total");
+    text_right(
+        &mut content,
+        BOLD,
+        10.0,
+        COLUMNS[2],
+        y,
+        &format_duration(invoice.duration()),
+    );
+    text_right(&mut content, BOLD, 10.0, COLUMNS[3], y, &total);
+    pages.push(content);
+
+    document(pages)
+}
+
+/// Writes the column headings of the items at the given position and
+/// returns the position of the first row.
+fn table_header(content: &mut Content, y: f32) -> f32 {
+    text(content, BOLD, 10.0, COLUMNS[0], y, "Date");
+    text(content, BOLD, 10.0, COLUMNS[1], y, "Description");
+    text_right(content, BOLD, 10.0, COLUMNS[2], y, "Hours");
+    text_right(content, BOLD, 10.0, COLUMNS[3], y, "Amount");
+    line(content, y - 5.0, 0.8);
+    y - ROW_HEIGHT
+}
+
+fn truncate(description: &str) -> String {
+    match description.chars().count() > MAX_DESCRIPTION {
+        true => {
+            let short: String = description.chars().take(MAX_DESCRIPTION - 3).collect();
+            short + "..."
+        }
+        false => description.to_string(),
+    }
+}
@@ -0,0 +1,48 @@
+//! Data model, storage and duration logic behind the `tracker` CLI.
+//!
+//! ```no_run
+//! use tracker::{Config, Database, DaySummary};
+//!
+//! let config = Config::parse_from_cfgdir()?;
+//! let db = Database::new(&config.storage_dir)?;
+//! let today = chrono::Local::now().date_naive();
+//! let summary = DaySummary::compute(&db.list(today)?, &config)?;
+//! println!("worked {} minutes today", summary.work.num_minutes());
+//! # anyhow::Ok(())
+//! ```
+
+// Every type used by the public API has to be re-exported below.
+#![warn(unnameable_types)]
+
+pub(crate) mod billing;
+pub mod cli;
+pub(crate) mod clock;
+mod commands;
+pub(crate) mod config;
+pub(crate) mod db;
+pub(crate) mod export;
+mod format;
+pub(crate) mod import;
+pub(crate) mod invoice;
+mod migration;
+pub(crate) mod model;
+mod pdf;
+pub(crate) mod rounding;
+pub(crate) mod storage;
+pub(crate) mod summary;
+mod text;
+pub(crate) mod timesheet;
+pub(crate) mod util;
+
+pub use config::{Client, Config, EntryFilter, LedgerAccount, Project};
+pub use db::{
+    BackupFile, Database, EntryChange, EntryProblem, HistoryAction, HistoryEntry, InvoiceRecord,
+    Lock, NewInvoice, Operation, SealViolation, SealedPeriod, Stats,
+};
+pub use model::{Change, DeletedEntry, Entry, NewEntry};
+pub use rounding::{Rounding, RoundingMode, RoundingUnit};
+pub use storage::{
+    HistoryStore, InvoiceStore, LockStore, OperationLog, SealStore, Storage, TrashStore,
+};
+pub use summary::{DaySummary, Interval};
+pub use util::{DateRange, Parsable};
@@ -102,18 +102,29 @@ pub(crate) fn sqlite_database(config: &Config) -> Result<()> {
 
     let db = Database::new(&config.storage_dir)?;
 
+    // Imported entry files are kept as backup instead of being removed.
+    let legacy_dir = db.backup_dir().join("legacy");
+    fs::create_dir_all(&legacy_dir)?;
+
     println!();
 
     for (i, v) in store.enumerate() {
         let (path, entries) = v?;
-        for entry in entries {
-            db.add(NewEntry {
-                timestamp: entry.timestamp,
-                message: entry.message,
-                long: entry.long,
-            })?;
-        }
-        fs::remove_file(path)?;
+        // Each file is imported as a whole, so that an interrupted migration
+        // can be resumed without duplicating entries.
+        db.transaction(|db| {
+            for entry in entries {
+                db.add(NewEntry {
+                    timestamp: entry.timestamp,
+                    message: entry.message,
+                    long: entry.long,
+                })?;
+            }
+            if let Some(name) = path.file_name() {
+                fs::rename(&path, legacy_dir.join(name))?;
+            }
+            Ok(())
+        })?;
         print!(
             "{}",
             format!("\rMigrated {i} of {count} entry files ...")
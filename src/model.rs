@@ -2,6 +2,7 @@ use anyhow::Result;
 use chrono::NaiveDateTime;
 use std::io::Write;
 
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct NewEntry {
     pub timestamp: NaiveDateTime,
     pub message: String,
@@ -16,7 +17,36 @@ pub struct Entry {
     pub long: Option<String>,
 }
 
+/// An entry which has been moved to the trash.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DeletedEntry {
+    pub entry: Entry,
+    pub deleted_at: NaiveDateTime,
+}
+
+/// A single modification of the stored entries.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Change {
+    Add(NewEntry),
+    Update(Entry),
+    Delete(u32),
+}
+
+impl NewEntry {
+    /// Returns a short description of the entry, e.g. for the
+    /// operation log.
+    pub fn summary(&self) -> String {
+        summary(&self.message, self.timestamp)
+    }
+}
+
 impl Entry {
+    /// Returns a short description of the entry, e.g. for the
+    /// operation log.
+    pub fn summary(&self) -> String {
+        summary(&self.message, self.timestamp)
+    }
+
     pub fn to_csv<W: Write>(&self, mut w: W) -> Result<()> {
         let long = match self.long {
             Some(ref v) => v.as_ref(),
@@ -32,3 +62,7 @@ impl Entry {
         Ok(())
     }
 }
+
+fn summary(message: &str, timestamp: NaiveDateTime) -> String {
+    format!("'{message}' at {}", timestamp.format("%Y-%m-%d %H:%M"))
+}
@@ -0,0 +1,106 @@
+//! Minimal A4 PDF documents using the standard fonts of PDF readers, so
+//! that no fonts have to be embedded.
+
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+
+pub const PAGE_WIDTH: f32 = 595.0;
+pub const PAGE_HEIGHT: f32 = 842.0;
+pub const MARGIN: f32 = 56.0;
+
+pub const REGULAR: Name = Name(b"F1");
+pub const BOLD: Name = Name(b"F2");
+
+/// Writes the given contents as a document with one A4 page each.
+pub fn document(pages: Vec<Content>) -> Vec<u8> {
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let regular_id = Ref::new(3);
+    let bold_id = Ref::new(4);
+    let page_ids: Vec<_> = (0..pages.len() as i32)
+        .map(|i| Ref::new(5 + 2 * i))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(pages.len() as i32);
+
+    for (page_id, content) in page_ids.into_iter().zip(pages) {
+        let content_id = Ref::new(page_id.get() + 1);
+        let mut page = pdf.page(page_id);
+        page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        page.parent(page_tree_id);
+        page.contents(content_id);
+        page.resources()
+            .fonts()
+            .pair(REGULAR, regular_id)
+            .pair(BOLD, bold_id);
+        page.finish();
+        pdf.stream(content_id, &content.finish());
+    }
+
+    for (id, font) in [(regular_id, "Helvetica"), (bold_id, "Helvetica-Bold")] {
+        pdf.type1_font(id)
+            .base_font(Name(font.as_bytes()))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+    }
+
+    pdf.finish()
+}
+
+pub fn text(content: &mut Content, font: Name, size: f32, x: f32, y: f32, text: &str) {
+    content.begin_text();
+    content.set_font(font, size);
+    content.next_line(x, y);
+    content.show(Str(&win_ansi(text)));
+    content.end_text();
+}
+
+/// Writes text ending at the given x position, e.g. for amounts.
+pub fn text_right(content: &mut Content, font: Name, size: f32, x: f32, y: f32, s: &str) {
+    text(
+        content,
+        font,
+        size,
+        x - text_width(s, size, font == BOLD),
+        y,
+        s,
+    );
+}
+
+/// Draws a horizontal line between the margins.
+pub fn line(content: &mut Content, y: f32, width: f32) {
+    content.set_line_width(width);
+    content.move_to(MARGIN, y);
+    content.line_to(PAGE_WIDTH - MARGIN, y);
+    content.stroke();
+}
+
+/// Estimates the width of text in the Helvetica fonts. Digits, which are
+/// all equally wide, and common punctuation are exact.
+fn text_width(text: &str, size: f32, bold: bool) -> f32 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c {
+            '0'..='9' => 556,
+            '.' | ',' | ' ' => 278,
+            ':' => 278 + if bold { 55 } else { 0 },
+            '-' => 333,
+            c if c.is_uppercase() => 667,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+/// Encodes text for the standard fonts. Characters beyond Latin-1 are
+/// replaced by `?`.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x20..=0x7E | 0xA0..=0xFF => c as u8,
+            _ => b'?',
+        })
+        .collect()
+}
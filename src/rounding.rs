@@ -0,0 +1,217 @@
+//! Rounding of billed durations, which keeps the tracked timestamps exact.
+
+use crate::summary::Interval;
+use chrono::{Duration, NaiveDate};
+use fancy_duration::FancyDuration;
+use serde::Deserialize;
+
+/// Identifies a unit by its day, its task and the index of its interval.
+type UnitKey<'a> = (NaiveDate, Option<&'a str>, usize);
+
+/// The direction durations are rounded in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundingMode {
+    #[default]
+    Up,
+    Down,
+    Nearest,
+}
+
+/// The unit whose summed up duration is rounded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundingUnit {
+    /// Each interval between two entries.
+    #[default]
+    Interval,
+    /// All intervals of a day.
+    Day,
+    /// All intervals of a day with the same message.
+    Task,
+}
+
+/// A policy for rounding billed durations, e.g. up to 15 minute increments
+/// with at least 30 minutes per day.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize)]
+pub struct Rounding {
+    #[serde(default)]
+    pub mode: RoundingMode,
+    pub step: Option<FancyDuration<Duration>>,
+    /// The least duration billed for each unit with tracked time.
+    pub minimum: Option<FancyDuration<Duration>>,
+    #[serde(default)]
+    pub per: RoundingUnit,
+}
+
+impl Rounding {
+    /// Rounds up to multiples of the step for each interval.
+    pub fn up(step: Duration) -> Self {
+        Self {
+            step: Some(FancyDuration(step)),
+            ..Self::default()
+        }
+    }
+
+    /// Rounds to the nearest multiple of the step for each interval.
+    pub fn nearest(step: Duration) -> Self {
+        Self {
+            mode: RoundingMode::Nearest,
+            ..Self::up(step)
+        }
+    }
+
+    /// Rounds a duration to a multiple of the step and raises it to the
+    /// minimum. Empty durations stay empty.
+    pub fn round(&self, duration: Duration) -> Duration {
+        if duration <= Duration::zero() {
+            return duration;
+        }
+        let mut rounded = duration;
+        if let Some(step) = self.step.as_ref().map(|s| s.duration())
+            && step > Duration::zero()
+        {
+            let (seconds, step) = (duration.num_seconds(), step.num_seconds());
+            let steps = match self.mode {
+                RoundingMode::Up => (seconds + step - 1) / step,
+                RoundingMode::Down => seconds / step,
+                RoundingMode::Nearest => (seconds + step / 2) / step,
+            };
+            rounded = Duration::seconds(steps * step);
+        }
+        match self.minimum.as_ref().map(|m| m.duration()) {
+            Some(minimum) => rounded.max(minimum),
+            None => rounded,
+        }
+    }
+
+    /// Returns the billed duration of each of the given intervals.
+    ///
+    /// The intervals are summed up and rounded per unit. The rounded
+    /// duration of a unit is split between its intervals in proportion to
+    /// their length, so that the billed durations add up per unit.
+    pub fn apply(&self, intervals: &[Interval]) -> Vec<Duration> {
+        let mut units: Vec<(UnitKey, Vec<usize>)> = vec![];
+        for (index, interval) in intervals.iter().enumerate() {
+            let date = interval.start.date();
+            let key = match self.per {
+                RoundingUnit::Interval => (date, None, index),
+                RoundingUnit::Day => (date, None, 0),
+                RoundingUnit::Task => (date, Some(interval.entry.message.as_str()), 0),
+            };
+            match units.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, members)) => members.push(index),
+                None => units.push((key, vec![index])),
+            }
+        }
+
+        let mut billed = vec![Duration::zero(); intervals.len()];
+        for (_, members) in units {
+            let exact: Duration = members.iter().map(|&i| intervals[i].duration()).sum();
+            let rounded = self.round(exact);
+            let mut remaining = rounded;
+            for (n, &i) in members.iter().enumerate() {
+                let share = match n + 1 == members.len() {
+                    true => remaining,
+                    false if exact > Duration::zero() => Duration::seconds(
+                        rounded.num_seconds() * intervals[i].duration().num_seconds()
+                            / exact.num_seconds(),
+                    ),
+                    false => Duration::zero(),
+                };
+                billed[i] = share;
+                remaining -= share;
+            }
+        }
+        billed
+    }
+}
+
+/// Returns the billed duration of each of the given intervals, which is
+/// their exact duration without a rounding policy.
+pub fn billed_durations(rounding: Option<&Rounding>, intervals: &[Interval]) -> Vec<Duration> {
+    match rounding {
+        Some(rounding) => rounding.apply(intervals),
+        None => intervals.iter().map(Interval::duration).collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Rounding, RoundingMode, RoundingUnit};
+    use crate::model::Entry;
+    use crate::summary::intervals;
+    use chrono::{Duration, NaiveDateTime};
+    use fancy_duration::FancyDuration;
+
+    fn entry(id: u32, date_time: &str, message: &str) -> Entry {
+        Entry {
+            id,
+            timestamp: NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").unwrap(),
+            message: message.to_string(),
+            long: None,
+        }
+    }
+
+    fn minutes(durations: Vec<Duration>) -> Vec<i64> {
+        durations.iter().map(|d| d.num_minutes()).collect()
+    }
+
+    #[test]
+    fn round_modes() {
+        let step = Duration::minutes(15);
+        let round = |mode, minutes| {
+            let rounding = Rounding {
+                mode,
+                ..Rounding::up(step)
+            };
+            rounding.round(Duration::minutes(minutes)).num_minutes()
+        };
+        assert_eq!(15, round(RoundingMode::Up, 1));
+        assert_eq!(30, round(RoundingMode::Up, 30));
+        assert_eq!(0, round(RoundingMode::Down, 14));
+        assert_eq!(30, round(RoundingMode::Down, 44));
+        assert_eq!(15, round(RoundingMode::Nearest, 22));
+        assert_eq!(30, round(RoundingMode::Nearest, 23));
+        assert_eq!(0, round(RoundingMode::Up, 0));
+
+        let minimum = Rounding {
+            minimum: Some(FancyDuration(Duration::minutes(30))),
+            ..Rounding::up(step)
+        };
+        assert_eq!(30, minimum.round(Duration::minutes(5)).num_minutes());
+        assert_eq!(45, minimum.round(Duration::minutes(35)).num_minutes());
+    }
+
+    #[test]
+    fn apply_per_unit() {
+        let entries = vec![
+            entry(1, "2025-07-24 09:00", "start"),
+            entry(2, "2025-07-24 09:10", "login"),
+            entry(3, "2025-07-24 09:20", "review"),
+            entry(4, "2025-07-24 09:25", "login"),
+            entry(5, "2025-07-25 09:00", "start"),
+            entry(6, "2025-07-25 09:05", "login"),
+        ];
+        let intervals: Vec<_> = intervals(&entries).collect();
+
+        let rounding = Rounding::up(Duration::minutes(15));
+        assert_eq!(vec![15, 15, 15, 15], minutes(rounding.apply(&intervals)));
+
+        let per_task = Rounding {
+            per: RoundingUnit::Task,
+            ..rounding.clone()
+        };
+        // The 15 minutes of both login intervals are split 10:5.
+        assert_eq!(vec![10, 15, 5, 15], minutes(per_task.apply(&intervals)));
+
+        let per_day = Rounding {
+            per: RoundingUnit::Day,
+            minimum: Some(FancyDuration(Duration::minutes(60))),
+            ..rounding
+        };
+        let billed = per_day.apply(&intervals);
+        assert_eq!(vec![24, 24, 12, 60], minutes(billed.clone()));
+        assert_eq!(Duration::minutes(60), billed[..3].iter().sum());
+    }
+}
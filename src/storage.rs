@@ -0,0 +1,150 @@
+use crate::db::{
+    EntryChange, HistoryEntry, InvoiceRecord, Lock, NewInvoice, Operation, SealViolation,
+    SealedPeriod,
+};
+use crate::model::{Change, DeletedEntry, Entry, NewEntry};
+use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::HashSet;
+
+mod memory;
+mod sqlite;
+
+pub use memory::MemoryStorage;
+
+/// A backend storing the tracked entries.
+///
+/// Commands only depend on this trait, so that other backends can be added
+/// without touching them. Besides the entries, a storage keeps the trash,
+/// the history, locks, the operation log, seals and invoices, as defined by
+/// the traits it extends. Backends return an error for features they do not
+/// support. Backups and maintenance of the database file are only provided
+/// by [`Database`](crate::db::Database) itself.
+pub trait Storage:
+    TrashStore + HistoryStore + LockStore + OperationLog + SealStore + InvoiceStore
+{
+    /// Adds a new entry and returns its ID.
+    fn add(&self, entry: NewEntry) -> Result<u32>;
+
+    /// Returns the entry with the given ID, if it exists.
+    fn get(&self, id: u32) -> Result<Option<Entry>>;
+
+    /// Returns all entries of the given day.
+    fn list(&self, date: NaiveDate) -> Result<Vec<Entry>>;
+
+    /// Returns all entries within the given days ordered by their timestamp.
+    fn list_range(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Entry>>;
+
+    /// Returns all entries ordered by their timestamp.
+    fn list_all(&self) -> Result<Vec<Entry>>;
+
+    /// Replaces the entry with the same ID.
+    fn update(&self, entry: Entry) -> Result<()>;
+
+    /// Moves the entry with the given ID to the trash.
+    fn delete(&self, id: u32) -> Result<()>;
+
+    /// Applies all given changes at once. Either all or none of them are
+    /// applied. The description names the changes, e.g. for undoing them.
+    fn apply(&self, description: &str, changes: Vec<Change>) -> Result<()>;
+}
+
+/// Deleted entries, which can be restored until they are purged.
+pub trait TrashStore {
+    /// Returns all entries in the trash, latest deleted first.
+    fn list_deleted(&self) -> Result<Vec<DeletedEntry>>;
+
+    /// Moves the entries with the given IDs out of the trash and records this
+    /// as one operation, which can be undone. Fails without restoring any
+    /// entry if one of them is not in the trash.
+    fn restore_deleted(&self, ids: &[u32]) -> Result<()>;
+
+    /// Permanently removes all entries which have been moved to the trash at
+    /// or before the given timestamp and returns their count. The changes of
+    /// these entries are removed from the operation log and undone
+    /// operations can not be redone afterwards.
+    fn purge_deleted_before(&self, timestamp: NaiveDateTime) -> Result<usize>;
+}
+
+/// All recorded versions of the entries.
+pub trait HistoryStore {
+    /// Returns all recorded versions of the entry with the given ID, oldest
+    /// first.
+    fn history(&self, id: u32) -> Result<Vec<HistoryEntry>>;
+
+    /// Returns the IDs of all entries which had a version dated within the
+    /// given range or, if `changed` is set, which have been changed within
+    /// the given range.
+    fn history_ids(&self, from: NaiveDate, to: NaiveDate, changed: bool) -> Result<Vec<u32>>;
+
+    /// Returns when the entry with the given ID has been created and last
+    /// updated, if it still exists and these are known.
+    #[allow(clippy::type_complexity)]
+    fn entry_times(
+        &self,
+        id: u32,
+    ) -> Result<Option<(Option<NaiveDateTime>, Option<NaiveDateTime>)>>;
+}
+
+/// Periods whose entries can only be changed by force.
+pub trait LockStore {
+    /// Locks all days within the given period.
+    fn lock(&self, start: NaiveDate, end: NaiveDate) -> Result<()>;
+
+    /// Unlocks all days within the given period and returns the number of
+    /// affected locks.
+    fn unlock(&self, start: NaiveDate, end: NaiveDate) -> Result<usize>;
+
+    /// Returns all locked periods ordered by date.
+    fn locks(&self) -> Result<Vec<Lock>>;
+}
+
+/// The log of applied changes, which can be undone and redone.
+pub trait OperationLog {
+    /// Returns up to `limit` operations in the order they would be undone
+    /// or, when `undone` is set, redone.
+    fn operations(&self, undone: bool, limit: usize) -> Result<Vec<Operation>>;
+
+    /// Returns the changes of the operation with the given ID in the order
+    /// they have been applied.
+    fn operation_changes(&self, id: u32) -> Result<Vec<EntryChange>>;
+
+    /// Reverts the last `count` operations and returns them, latest first.
+    /// Added entries are moved to the trash.
+    fn undo(&self, count: usize) -> Result<Vec<Operation>>;
+
+    /// Re-applies the last `count` undone operations and returns them,
+    /// earliest first.
+    fn redo(&self, count: usize) -> Result<Vec<Operation>>;
+}
+
+/// Tamper-evident hashes of closed periods.
+pub trait SealStore {
+    /// Seals all entries within the given period.
+    fn seal(&self, start: NaiveDate, end: NaiveDate) -> Result<SealedPeriod>;
+
+    /// Returns all seals in the order they have been created.
+    fn seals(&self) -> Result<Vec<SealedPeriod>>;
+
+    /// Verifies all seals against the current entries and returns all found
+    /// violations.
+    fn verify_seals(&self) -> Result<Vec<SealViolation>>;
+}
+
+/// Issued invoices and the entries billed by them.
+pub trait InvoiceStore {
+    /// Records an invoice over the given entries, so that they are not
+    /// billed again, and passes the numbered invoice to `issue`. Nothing is
+    /// recorded if `issue` fails.
+    fn record_invoice(
+        &self,
+        invoice: NewInvoice,
+        issue: &mut dyn FnMut(&InvoiceRecord) -> Result<()>,
+    ) -> Result<InvoiceRecord>;
+
+    /// Returns all invoices in the order they have been issued.
+    fn invoices(&self) -> Result<Vec<InvoiceRecord>>;
+
+    /// Returns the IDs of all entries which have already been invoiced.
+    fn invoiced_entries(&self) -> Result<HashSet<u32>>;
+}
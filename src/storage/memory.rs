@@ -0,0 +1,663 @@
+use super::{HistoryStore, InvoiceStore, LockStore, OperationLog, SealStore, Storage, TrashStore};
+use crate::clock;
+use crate::db::{
+    EntryChange, HistoryEntry, InvoiceRecord, Lock, NewInvoice, Operation, SealViolation,
+    SealedPeriod, next_invoice_number, restore_description,
+};
+use crate::model::{Change, DeletedEntry, Entry, NewEntry};
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+
+/// A storage keeping all entries in memory, e.g. for tests and dry runs.
+///
+/// Besides the entries, it keeps the trash, locks, the operation log and
+/// invoices. The history and seals are not supported.
+#[derive(Default)]
+pub struct MemoryStorage {
+    state: RefCell<State>,
+    force: bool,
+}
+
+#[derive(Clone, Default)]
+struct State {
+    entries: BTreeMap<u32, Entry>,
+    trash: BTreeMap<u32, DeletedEntry>,
+    locks: Vec<Lock>,
+    operations: Vec<LoggedOperation>,
+    invoices: Vec<InvoiceRecord>,
+    invoiced: HashSet<u32>,
+    /// The highest ID given to an entry so far, which is never reused.
+    last_id: u32,
+}
+
+#[derive(Clone)]
+struct LoggedOperation {
+    operation: Operation,
+    undone: bool,
+    changes: Vec<EntryChange>,
+}
+
+impl MemoryStorage {
+    /// Creates a storage containing a copy of the entries, the trash, the
+    /// locks, the operation log and the invoices of the given storage.
+    pub fn copy_of(storage: &dyn Storage) -> Result<Self> {
+        let mut operations = vec![];
+        for undone in [false, true] {
+            for operation in storage.operations(undone, usize::MAX)? {
+                operations.push(LoggedOperation {
+                    changes: storage.operation_changes(operation.id)?,
+                    operation,
+                    undone,
+                });
+            }
+        }
+        operations.sort_by_key(|o| o.operation.id);
+
+        let mut state = State {
+            entries: storage.list_all()?.into_iter().map(|e| (e.id, e)).collect(),
+            trash: storage
+                .list_deleted()?
+                .into_iter()
+                .map(|d| (d.entry.id, d))
+                .collect(),
+            locks: storage.locks()?,
+            operations,
+            invoices: storage.invoices()?,
+            invoiced: storage.invoiced_entries()?,
+            last_id: 0,
+        };
+        state.last_id = state
+            .entries
+            .keys()
+            .chain(state.trash.keys())
+            .chain(state.invoiced.iter())
+            .chain(
+                state
+                    .operations
+                    .iter()
+                    .flat_map(|o| o.changes.iter().map(|c| &c.entry_id)),
+            )
+            .copied()
+            .max()
+            .unwrap_or(0);
+        Ok(Self {
+            state: RefCell::new(state),
+            force: false,
+        })
+    }
+
+    /// Allows changing entries of locked periods.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    fn filtered(&self, f: impl Fn(&Entry) -> bool) -> Vec<Entry> {
+        let mut entries: Vec<_> = self
+            .state
+            .borrow()
+            .entries
+            .values()
+            .filter(|e| f(e))
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| e.timestamp);
+        entries
+    }
+
+    fn existing(&self, id: u32) -> Result<Entry> {
+        self.state
+            .borrow()
+            .entries
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("no entry with id {id}"))
+    }
+
+    /// Runs the given closure and reverts all its changes if it fails.
+    fn atomic<T>(&self, f: impl FnOnce(&Self) -> Result<T>) -> Result<T> {
+        let snapshot = self.state.borrow().clone();
+        let res = f(self);
+        if res.is_err() {
+            *self.state.borrow_mut() = snapshot;
+        }
+        res
+    }
+
+    /// Returns an error if any of the given dates is locked, unless changes
+    /// are forced.
+    fn check_locked(&self, dates: &[NaiveDate]) -> Result<()> {
+        if self.force {
+            return Ok(());
+        }
+        let state = self.state.borrow();
+        for &date in dates {
+            if let Some(lock) = state
+                .locks
+                .iter()
+                .find(|l| l.start <= date && date <= l.end)
+            {
+                return Err(anyhow::anyhow!(
+                    "{date} is locked by the period {}..{}; \
+                    use --force to change it anyway",
+                    lock.start,
+                    lock.end
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the date of the entry with the given ID, including entries
+    /// in the trash.
+    fn entry_date(&self, id: u32) -> Option<NaiveDate> {
+        let state = self.state.borrow();
+        state
+            .entries
+            .get(&id)
+            .or_else(|| state.trash.get(&id).map(|d| &d.entry))
+            .map(|e| e.timestamp.date())
+    }
+
+    /// Sets the entry to the given state, re-creating it if it has been
+    /// removed or moved to the trash.
+    fn restore(&self, entry: &Entry) -> Result<()> {
+        let mut dates = vec![entry.timestamp.date()];
+        dates.extend(self.entry_date(entry.id));
+        self.check_locked(&dates)?;
+        let mut state = self.state.borrow_mut();
+        state.trash.remove(&entry.id);
+        state.entries.insert(entry.id, entry.clone());
+        Ok(())
+    }
+
+    /// Permanently removes the entry with the given ID along with its
+    /// changes in the operation log. Undone operations can not be redone
+    /// afterwards.
+    fn purge(&self, id: u32) -> Result<()> {
+        self.check_locked(self.entry_date(id).as_slice())?;
+        let mut state = self.state.borrow_mut();
+        state.entries.remove(&id);
+        state.trash.remove(&id);
+        state.operations.retain_mut(|o| {
+            o.changes.retain(|c| c.entry_id != id);
+            !o.undone && !o.changes.is_empty()
+        });
+        Ok(())
+    }
+
+    /// Records the changes as a new operation. Operations which have been
+    /// undone before can not be redone afterwards.
+    fn log(&self, description: &str, changes: Vec<EntryChange>) {
+        let mut state = self.state.borrow_mut();
+        state.operations.retain(|o| !o.undone);
+        let id = state.operations.last().map_or(1, |o| o.operation.id + 1);
+        state.operations.push(LoggedOperation {
+            operation: Operation {
+                id,
+                created_at: clock::now(),
+                description: description.to_string(),
+            },
+            undone: false,
+            changes,
+        });
+    }
+
+    fn set_undone(&self, id: u32, undone: bool) {
+        let mut state = self.state.borrow_mut();
+        if let Some(op) = state.operations.iter_mut().find(|o| o.operation.id == id) {
+            op.undone = undone;
+        }
+    }
+}
+
+fn unsupported(feature: &str) -> anyhow::Error {
+    anyhow::anyhow!("{feature} is not available in the in-memory storage used by --dry-run")
+}
+
+impl Storage for MemoryStorage {
+    fn add(&self, entry: NewEntry) -> Result<u32> {
+        self.check_locked(&[entry.timestamp.date()])?;
+        let mut state = self.state.borrow_mut();
+        state.last_id += 1;
+        let id = state.last_id;
+        state.entries.insert(
+            id,
+            Entry {
+                id,
+                timestamp: entry.timestamp,
+                message: entry.message,
+                long: entry.long,
+            },
+        );
+        Ok(id)
+    }
+
+    fn get(&self, id: u32) -> Result<Option<Entry>> {
+        Ok(self.state.borrow().entries.get(&id).cloned())
+    }
+
+    fn list(&self, date: NaiveDate) -> Result<Vec<Entry>> {
+        Ok(self.filtered(|e| e.timestamp.date() == date))
+    }
+
+    fn list_range(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Entry>> {
+        Ok(self.filtered(|e| (from..=to).contains(&e.timestamp.date())))
+    }
+
+    fn list_all(&self) -> Result<Vec<Entry>> {
+        Ok(self.filtered(|_| true))
+    }
+
+    fn update(&self, entry: Entry) -> Result<()> {
+        let old = self.existing(entry.id)?;
+        self.check_locked(&[entry.timestamp.date(), old.timestamp.date()])?;
+        self.state.borrow_mut().entries.insert(entry.id, entry);
+        Ok(())
+    }
+
+    fn delete(&self, id: u32) -> Result<()> {
+        let entry = self.existing(id)?;
+        self.check_locked(&[entry.timestamp.date()])?;
+        let mut state = self.state.borrow_mut();
+        state.entries.remove(&id);
+        state.trash.insert(
+            id,
+            DeletedEntry {
+                entry,
+                deleted_at: clock::now(),
+            },
+        );
+        Ok(())
+    }
+
+    fn apply(&self, description: &str, changes: Vec<Change>) -> Result<()> {
+        self.atomic(|storage| {
+            let mut recorded = vec![];
+            for change in changes {
+                let (old, new) = match change {
+                    Change::Add(entry) => {
+                        let id = storage.add(entry)?;
+                        (None, storage.get(id)?)
+                    }
+                    Change::Update(entry) => {
+                        let old = storage.existing(entry.id)?;
+                        storage.update(entry.clone())?;
+                        (Some(old), Some(entry))
+                    }
+                    Change::Delete(id) => {
+                        let old = storage.existing(id)?;
+                        storage.delete(id)?;
+                        (Some(old), None)
+                    }
+                };
+                recorded.push(EntryChange {
+                    entry_id: old.as_ref().or(new.as_ref()).map_or(0, |e| e.id),
+                    old,
+                    new,
+                });
+            }
+
+            storage.log(description, recorded);
+            Ok(())
+        })
+    }
+}
+
+impl TrashStore for MemoryStorage {
+    fn list_deleted(&self) -> Result<Vec<DeletedEntry>> {
+        let mut deleted: Vec<_> = self.state.borrow().trash.values().cloned().collect();
+        deleted.sort_by_key(|d| (std::cmp::Reverse(d.deleted_at), d.entry.timestamp));
+        Ok(deleted)
+    }
+
+    fn restore_deleted(&self, ids: &[u32]) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.atomic(|storage| {
+            let mut entries = vec![];
+            for &id in ids {
+                let deleted = storage.state.borrow().trash.get(&id).cloned();
+                let Some(deleted) = deleted else {
+                    return Err(anyhow::anyhow!("no entry with id {id} in the trash"));
+                };
+                storage.restore(&deleted.entry)?;
+                entries.push(deleted.entry);
+            }
+
+            let changes = entries
+                .iter()
+                .map(|entry| EntryChange {
+                    entry_id: entry.id,
+                    old: None,
+                    new: Some(entry.clone()),
+                })
+                .collect();
+            storage.log(&restore_description(&entries), changes);
+            Ok(())
+        })
+    }
+
+    fn purge_deleted_before(&self, timestamp: NaiveDateTime) -> Result<usize> {
+        let ids: Vec<_> = self
+            .list_deleted()?
+            .into_iter()
+            .filter(|d| d.deleted_at <= timestamp)
+            .map(|d| d.entry.id)
+            .filter(|&id| self.check_locked(self.entry_date(id).as_slice()).is_ok())
+            .collect();
+        for &id in &ids {
+            self.purge(id)?;
+        }
+        Ok(ids.len())
+    }
+}
+
+impl HistoryStore for MemoryStorage {
+    fn history(&self, _id: u32) -> Result<Vec<HistoryEntry>> {
+        Err(unsupported("the history"))
+    }
+
+    fn history_ids(&self, _from: NaiveDate, _to: NaiveDate, _changed: bool) -> Result<Vec<u32>> {
+        Err(unsupported("the history"))
+    }
+
+    fn entry_times(
+        &self,
+        _id: u32,
+    ) -> Result<Option<(Option<NaiveDateTime>, Option<NaiveDateTime>)>> {
+        Err(unsupported("the history"))
+    }
+}
+
+impl LockStore for MemoryStorage {
+    fn lock(&self, start: NaiveDate, end: NaiveDate) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        let id = state
+            .locks
+            .iter()
+            .map(|l| l.id)
+            .max()
+            .map_or(1, |id| id + 1);
+        state.locks.push(Lock {
+            id,
+            start,
+            end,
+            locked_at: clock::now(),
+        });
+        Ok(())
+    }
+
+    fn unlock(&self, start: NaiveDate, end: NaiveDate) -> Result<usize> {
+        let mut state = self.state.borrow_mut();
+        let (overlapping, mut locks): (Vec<_>, Vec<_>) = state
+            .locks
+            .drain(..)
+            .partition(|l| l.start <= end && l.end >= start);
+
+        for lock in &overlapping {
+            let remaining = [
+                (lock.start, start - Duration::days(1)),
+                (end + Duration::days(1), lock.end),
+            ];
+            for (s, e) in remaining.into_iter().filter(|(s, e)| s <= e) {
+                let id = locks
+                    .iter()
+                    .chain(&overlapping)
+                    .map(|l| l.id)
+                    .max()
+                    .map_or(1, |id| id + 1);
+                locks.push(Lock {
+                    id,
+                    start: s,
+                    end: e,
+                    locked_at: lock.locked_at,
+                });
+            }
+        }
+
+        state.locks = locks;
+        Ok(overlapping.len())
+    }
+
+    fn locks(&self) -> Result<Vec<Lock>> {
+        let mut locks = self.state.borrow().locks.clone();
+        locks.sort_by_key(|l| (l.start, l.end));
+        Ok(locks)
+    }
+}
+
+impl OperationLog for MemoryStorage {
+    fn operations(&self, undone: bool, limit: usize) -> Result<Vec<Operation>> {
+        let state = self.state.borrow();
+        let ops = state
+            .operations
+            .iter()
+            .filter(|o| o.undone == undone)
+            .map(|o| o.operation.clone());
+        Ok(match undone {
+            true => ops.take(limit).collect(),
+            false => ops.rev().take(limit).collect(),
+        })
+    }
+
+    fn operation_changes(&self, id: u32) -> Result<Vec<EntryChange>> {
+        Ok(self
+            .state
+            .borrow()
+            .operations
+            .iter()
+            .find(|o| o.operation.id == id)
+            .map(|o| o.changes.clone())
+            .unwrap_or_default())
+    }
+
+    fn undo(&self, count: usize) -> Result<Vec<Operation>> {
+        self.atomic(|storage| {
+            let ops = storage.operations(false, count)?;
+            for op in &ops {
+                for change in storage.operation_changes(op.id)?.iter().rev() {
+                    match change.old {
+                        Some(ref entry) => storage.restore(entry)?,
+                        None => storage.delete(change.entry_id)?,
+                    }
+                }
+                storage.set_undone(op.id, true);
+            }
+            Ok(ops)
+        })
+    }
+
+    fn redo(&self, count: usize) -> Result<Vec<Operation>> {
+        self.atomic(|storage| {
+            let ops = storage.operations(true, count)?;
+            for op in &ops {
+                for change in storage.operation_changes(op.id)? {
+                    match change.new {
+                        Some(ref entry) => storage.restore(entry)?,
+                        None => storage.delete(change.entry_id)?,
+                    }
+                }
+                storage.set_undone(op.id, false);
+            }
+            Ok(ops)
+        })
+    }
+}
+
+impl SealStore for MemoryStorage {
+    fn seal(&self, _start: NaiveDate, _end: NaiveDate) -> Result<SealedPeriod> {
+        Err(unsupported("sealing"))
+    }
+
+    fn seals(&self) -> Result<Vec<SealedPeriod>> {
+        Err(unsupported("sealing"))
+    }
+
+    fn verify_seals(&self) -> Result<Vec<SealViolation>> {
+        Err(unsupported("sealing"))
+    }
+}
+
+impl InvoiceStore for MemoryStorage {
+    fn record_invoice(
+        &self,
+        invoice: NewInvoice,
+        issue: &mut dyn FnMut(&InvoiceRecord) -> Result<()>,
+    ) -> Result<InvoiceRecord> {
+        let record = {
+            let state = self.state.borrow();
+            if let Some(id) = invoice
+                .entry_ids
+                .iter()
+                .find(|id| state.invoiced.contains(id))
+            {
+                return Err(anyhow::anyhow!("entry {id} has already been invoiced"));
+            }
+            let issued_at = clock::now();
+            InvoiceRecord {
+                id: state.invoices.len() as u32 + 1,
+                number: next_invoice_number(issued_at, &state.invoices),
+                client: invoice.client,
+                start: invoice.start,
+                end: invoice.end,
+                issued_at,
+                total: invoice.total,
+                currency: invoice.currency,
+            }
+        };
+        issue(&record)?;
+
+        let mut state = self.state.borrow_mut();
+        state.invoices.push(record.clone());
+        state.invoiced.extend(invoice.entry_ids);
+        Ok(record)
+    }
+
+    fn invoices(&self) -> Result<Vec<InvoiceRecord>> {
+        Ok(self.state.borrow().invoices.clone())
+    }
+
+    fn invoiced_entries(&self) -> Result<HashSet<u32>> {
+        Ok(self.state.borrow().invoiced.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MemoryStorage;
+    use crate::model::{Change, Entry, NewEntry};
+    use crate::storage::{LockStore, OperationLog, Storage, TrashStore};
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    fn timestamp(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn new_entry(ts: &str, message: &str) -> NewEntry {
+        NewEntry {
+            timestamp: timestamp(ts),
+            message: message.to_string(),
+            long: None,
+        }
+    }
+
+    #[test]
+    fn add_list_update_delete() {
+        let storage = MemoryStorage::default();
+        let b = storage.add(new_entry("2025-07-24 12:00", "break")).unwrap();
+        let a = storage.add(new_entry("2025-07-24 09:00", "start")).unwrap();
+        storage.add(new_entry("2025-07-25 09:00", "start")).unwrap();
+
+        let day = NaiveDate::from_ymd_opt(2025, 7, 24).unwrap();
+        let ids: Vec<_> = storage.list(day).unwrap().iter().map(|e| e.id).collect();
+        assert_eq!(vec![a, b], ids);
+        assert_eq!(
+            3,
+            storage
+                .list_range(day, day.succ_opt().unwrap())
+                .unwrap()
+                .len()
+        );
+
+        storage
+            .update(Entry {
+                message: "lunch".to_string(),
+                ..storage.get(b).unwrap().unwrap()
+            })
+            .unwrap();
+        assert_eq!("lunch", storage.get(b).unwrap().unwrap().message);
+
+        storage.delete(a).unwrap();
+        assert!(storage.get(a).unwrap().is_none());
+        assert!(storage.delete(a).is_err());
+
+        // IDs of purged entries are not given to new ones.
+        storage.delete(b).unwrap();
+        storage.purge_deleted_before(NaiveDateTime::MAX).unwrap();
+        let c = storage.add(new_entry("2025-07-24 13:00", "end")).unwrap();
+        assert_eq!(4, c);
+    }
+
+    #[test]
+    fn apply_is_atomic() {
+        let storage = MemoryStorage::default();
+        let id = storage.add(new_entry("2025-07-24 09:00", "start")).unwrap();
+
+        let res = storage.apply(
+            "test",
+            vec![
+                Change::Add(new_entry("2025-07-24 10:00", "meeting")),
+                Change::Delete(id),
+                Change::Delete(42),
+            ],
+        );
+
+        assert!(res.is_err());
+        assert!(storage.get(id).unwrap().is_some());
+        assert_eq!(
+            1,
+            storage
+                .list_range(NaiveDate::MIN, NaiveDate::MAX)
+                .unwrap()
+                .len()
+        );
+        assert!(storage.operations(false, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn trash_locks_and_undo() {
+        let storage = MemoryStorage::default();
+        let day = NaiveDate::from_ymd_opt(2025, 7, 24).unwrap();
+        storage
+            .apply(
+                "add start",
+                vec![Change::Add(new_entry("2025-07-24 09:00", "start"))],
+            )
+            .unwrap();
+        storage
+            .apply("delete start", vec![Change::Delete(1)])
+            .unwrap();
+        assert_eq!(1, storage.list_deleted().unwrap().len());
+
+        storage.lock(day, day).unwrap();
+        let err = storage.restore_deleted(&[1]).unwrap_err();
+        assert!(err.to_string().contains("2025-07-24 is locked"));
+        assert!(storage.undo(1).is_err());
+        assert_eq!(2, storage.operations(false, 2).unwrap().len());
+
+        assert_eq!(1, storage.unlock(day, day).unwrap());
+        let undone = storage.undo(1).unwrap();
+        assert_eq!("delete start", undone[0].description);
+        assert!(storage.get(1).unwrap().is_some());
+        assert!(storage.list_deleted().unwrap().is_empty());
+
+        storage.redo(1).unwrap();
+        assert!(storage.get(1).unwrap().is_none());
+        assert!(storage.restore_deleted(&[1, 2]).is_err());
+        assert_eq!(1, storage.list_deleted().unwrap().len());
+    }
+}
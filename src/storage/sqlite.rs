@@ -0,0 +1,142 @@
+use super::{HistoryStore, InvoiceStore, LockStore, OperationLog, SealStore, Storage, TrashStore};
+use crate::db::{
+    Database, EntryChange, HistoryEntry, InvoiceRecord, Lock, NewInvoice, Operation, SealViolation,
+    SealedPeriod,
+};
+use crate::model::{Change, DeletedEntry, Entry, NewEntry};
+use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::HashSet;
+
+impl Storage for Database {
+    fn add(&self, entry: NewEntry) -> Result<u32> {
+        Database::add(self, entry)
+    }
+
+    fn get(&self, id: u32) -> Result<Option<Entry>> {
+        Database::get(self, id)
+    }
+
+    fn list(&self, date: NaiveDate) -> Result<Vec<Entry>> {
+        Database::list(self, date)
+    }
+
+    fn list_range(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Entry>> {
+        Database::list_range(self, from, to)
+    }
+
+    fn list_all(&self) -> Result<Vec<Entry>> {
+        Database::list_all(self)
+    }
+
+    fn update(&self, entry: Entry) -> Result<()> {
+        Database::update(self, entry)
+    }
+
+    fn delete(&self, id: u32) -> Result<()> {
+        Database::delete(self, id)
+    }
+
+    fn apply(&self, description: &str, changes: Vec<Change>) -> Result<()> {
+        Database::apply(self, description, changes)
+    }
+}
+
+impl TrashStore for Database {
+    fn list_deleted(&self) -> Result<Vec<DeletedEntry>> {
+        Database::list_deleted(self)
+    }
+
+    fn restore_deleted(&self, ids: &[u32]) -> Result<()> {
+        Database::restore_deleted(self, ids)
+    }
+
+    fn purge_deleted_before(&self, timestamp: NaiveDateTime) -> Result<usize> {
+        Database::purge_deleted_before(self, timestamp)
+    }
+}
+
+impl HistoryStore for Database {
+    fn history(&self, id: u32) -> Result<Vec<HistoryEntry>> {
+        Database::history(self, id)
+    }
+
+    fn history_ids(&self, from: NaiveDate, to: NaiveDate, changed: bool) -> Result<Vec<u32>> {
+        Database::history_ids(self, from, to, changed)
+    }
+
+    fn entry_times(
+        &self,
+        id: u32,
+    ) -> Result<Option<(Option<NaiveDateTime>, Option<NaiveDateTime>)>> {
+        Database::entry_times(self, id)
+    }
+}
+
+impl LockStore for Database {
+    fn lock(&self, start: NaiveDate, end: NaiveDate) -> Result<()> {
+        Database::lock(self, start, end)
+    }
+
+    fn unlock(&self, start: NaiveDate, end: NaiveDate) -> Result<usize> {
+        Database::unlock(self, start, end)
+    }
+
+    fn locks(&self) -> Result<Vec<Lock>> {
+        Database::locks(self)
+    }
+}
+
+impl OperationLog for Database {
+    fn operations(&self, undone: bool, limit: usize) -> Result<Vec<Operation>> {
+        Database::operations(self, undone, limit)
+    }
+
+    fn operation_changes(&self, id: u32) -> Result<Vec<EntryChange>> {
+        Database::operation_changes(self, id)
+    }
+
+    fn undo(&self, count: usize) -> Result<Vec<Operation>> {
+        Database::undo(self, count)
+    }
+
+    fn redo(&self, count: usize) -> Result<Vec<Operation>> {
+        Database::redo(self, count)
+    }
+}
+
+impl SealStore for Database {
+    fn seal(&self, start: NaiveDate, end: NaiveDate) -> Result<SealedPeriod> {
+        Database::seal(self, start, end)
+    }
+
+    fn seals(&self) -> Result<Vec<SealedPeriod>> {
+        Database::seals(self)
+    }
+
+    fn verify_seals(&self) -> Result<Vec<SealViolation>> {
+        Database::verify_seals(self)
+    }
+}
+
+impl InvoiceStore for Database {
+    fn record_invoice(
+        &self,
+        invoice: NewInvoice,
+        issue: &mut dyn FnMut(&InvoiceRecord) -> Result<()>,
+    ) -> Result<InvoiceRecord> {
+        self.transaction(|db| {
+            let record = db.record_invoice(&invoice)?;
+            issue(&record)?;
+            Ok(record)
+        })
+    }
+
+    fn invoices(&self) -> Result<Vec<InvoiceRecord>> {
+        Database::invoices(self)
+    }
+
+    fn invoiced_entries(&self) -> Result<HashSet<u32>> {
+        Database::invoiced_entries(self)
+    }
+}
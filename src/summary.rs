@@ -0,0 +1,302 @@
+use crate::billing::Billing;
+use crate::config::Config;
+use crate::model::Entry;
+use crate::rounding::{Rounding, billed_durations};
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use std::ops::AddAssign;
+
+/// The summed up work and pause time of tracked entries.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DaySummary {
+    pub work: Duration,
+    pub pause: Duration,
+    /// The part of the work time which is billable, after rounding.
+    pub billable: Duration,
+    /// The amount in cents earned by the billable time.
+    pub revenue: i64,
+}
+
+impl DaySummary {
+    /// Computes the work and pause time of the given entries of a single
+    /// day, ordered by their timestamp.
+    ///
+    /// The time between two entries counts as pause if the later entry
+    /// matches the configured `break_regex` and as work otherwise. Work is
+    /// billable as decided by [`Billing::of`] the later entry and rounded by
+    /// its policy.
+    pub fn compute(entries: &[Entry], config: &Config) -> Result<Self> {
+        let mut summary = Self::default();
+        let mut billable: Vec<(Option<Rounding>, Vec<Interval>, Vec<Billing>)> = vec![];
+        for interval in intervals(entries) {
+            if interval.is_break(config)? {
+                summary.pause += interval.duration();
+                continue;
+            }
+            summary.work += interval.duration();
+            let billing = Billing::of(interval.entry, config)?;
+            if !billing.billable {
+                continue;
+            }
+            match billable.iter_mut().find(|(r, _, _)| *r == billing.rounding) {
+                Some((_, intervals, billings)) => {
+                    intervals.push(interval);
+                    billings.push(billing);
+                }
+                None => billable.push((billing.rounding.clone(), vec![interval], vec![billing])),
+            }
+        }
+
+        for (rounding, intervals, billings) in billable {
+            let billed = billed_durations(rounding.as_ref(), &intervals);
+            for (billing, duration) in billings.iter().zip(billed) {
+                summary.billable += duration;
+                summary.revenue += billing.amount(duration);
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Returns the share of billable time in the work time in percent.
+    pub fn utilization(&self) -> f64 {
+        match self.work.num_seconds() {
+            0 => 0.0,
+            work => self.billable.num_seconds() as f64 / work as f64 * 100.0,
+        }
+    }
+}
+
+impl Default for DaySummary {
+    fn default() -> Self {
+        Self {
+            work: Duration::zero(),
+            pause: Duration::zero(),
+            billable: Duration::zero(),
+            revenue: 0,
+        }
+    }
+}
+
+impl AddAssign for DaySummary {
+    fn add_assign(&mut self, rhs: Self) {
+        self.work += rhs.work;
+        self.pause += rhs.pause;
+        self.billable += rhs.billable;
+        self.revenue += rhs.revenue;
+    }
+}
+
+/// A calendar period to sum up days by.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Period {
+    /// An ISO week starting on Monday.
+    Week,
+    Month,
+}
+
+impl Period {
+    /// Returns the first day of the period containing the date.
+    pub fn start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Week => date.week(chrono::Weekday::Mon).first_day(),
+            Self::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// Returns the label of the period starting at the date, e.g. `2025-W30`
+    /// or `2025-07`.
+    pub fn label(&self, start: NaiveDate) -> String {
+        match self {
+            Self::Week => start.format("%G-W%V").to_string(),
+            Self::Month => start.format("%Y-%m").to_string(),
+        }
+    }
+}
+
+/// Sums up the given entries, ordered by their timestamp, per period. Each
+/// summary is returned with the first day of its period.
+pub fn period_summaries(
+    entries: &[Entry],
+    config: &Config,
+    period: Period,
+) -> Result<Vec<(NaiveDate, DaySummary)>> {
+    let mut summaries: Vec<(NaiveDate, DaySummary)> = vec![];
+    for day in entries.chunk_by(|a, b| a.timestamp.date() == b.timestamp.date()) {
+        let start = period.start(day[0].timestamp.date());
+        let summary = DaySummary::compute(day, config)?;
+        match summaries.last_mut() {
+            Some((s, total)) if *s == start => *total += summary,
+            _ => summaries.push((start, summary)),
+        }
+    }
+    Ok(summaries)
+}
+
+/// The time between two consecutive entries of a day, which is described
+/// by the later entry.
+#[derive(Clone, Copy, Debug)]
+pub struct Interval<'a> {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub entry: &'a Entry,
+}
+
+impl Interval<'_> {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// Returns whether the interval has been a break according to the
+    /// configured `break_regex`.
+    pub fn is_break(&self, config: &Config) -> Result<bool> {
+        self.entry.message_matches(&config.break_regex)
+    }
+}
+
+/// Returns the intervals between the given entries, ordered by their
+/// timestamp. Entries are only paired within the same day.
+pub fn intervals(entries: &[Entry]) -> impl Iterator<Item = Interval<'_>> {
+    entries
+        .chunk_by(|a, b| a.timestamp.date() == b.timestamp.date())
+        .flat_map(|day| day.windows(2))
+        .map(|pair| Interval {
+            start: pair[0].timestamp,
+            end: pair[1].timestamp,
+            entry: &pair[1],
+        })
+}
+
+/// Returns the time since the previous entry for each of the given entries,
+/// which is `None` for the first one.
+pub fn durations(entries: &[Entry]) -> impl Iterator<Item = Option<Duration>> + '_ {
+    let previous = std::iter::once(None).chain(entries.iter().map(Some));
+    entries
+        .iter()
+        .zip(previous)
+        .map(|(e, prev)| prev.map(|p| e.timestamp - p.timestamp))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DaySummary, Period, durations, intervals, period_summaries};
+    use crate::config::Config;
+    use crate::model::Entry;
+    use chrono::{Duration, NaiveDateTime};
+    use figment::Figment;
+    use figment::providers::{Format, Toml};
+
+    fn entry(time: &str, message: &str) -> Entry {
+        Entry {
+            id: 0,
+            timestamp: NaiveDateTime::parse_from_str(
+                &format!("2025-07-24 {time}"),
+                "%Y-%m-%d %H:%M",
+            )
+            .unwrap(),
+            message: message.to_string(),
+            long: None,
+        }
+    }
+
+    #[test]
+    fn compute_summary() {
+        let config: Config = Figment::new().extract().unwrap();
+        let entries = vec![
+            entry("09:00", "start"),
+            entry("12:00", "coding"),
+            entry("12:45", "break"),
+            entry("17:00", "end"),
+        ];
+
+        assert_eq!(
+            vec![
+                None,
+                Some(Duration::hours(3)),
+                Some(Duration::minutes(45)),
+                Some(Duration::minutes(255))
+            ],
+            durations(&entries).collect::<Vec<_>>()
+        );
+
+        let summary = DaySummary::compute(&entries, &config).unwrap();
+        assert_eq!(Duration::minutes(435), summary.work);
+        assert_eq!(Duration::minutes(45), summary.pause);
+
+        let mut two_days = entries.clone();
+        two_days.push(Entry {
+            timestamp: entries[0].timestamp + Duration::days(1),
+            ..entry("09:00", "start")
+        });
+        two_days.push(Entry {
+            timestamp: entries[1].timestamp + Duration::days(1),
+            ..entry("12:00", "coding")
+        });
+        let spans: Vec<_> = intervals(&two_days)
+            .map(|i| (i.entry.message.as_str(), i.duration().num_minutes()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("coding", 180),
+                ("break", 45),
+                ("end", 255),
+                ("coding", 180)
+            ],
+            spans
+        );
+
+        let empty = DaySummary::compute(&[], &config).unwrap();
+        assert_eq!(DaySummary::default(), empty);
+    }
+
+    #[test]
+    fn billable_time_per_period() {
+        let config: Config = Figment::new()
+            .merge(Toml::string(
+                r#"
+                [[projects]]
+                name = "Website"
+                tag = "web"
+                rate = 60
+                "#,
+            ))
+            .extract()
+            .unwrap();
+        let at = |date_time: &str, message: &str| Entry {
+            timestamp: NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").unwrap(),
+            ..entry("00:00", message)
+        };
+        let entries = vec![
+            at("2025-07-31 09:00", "start"),
+            at("2025-07-31 10:30", "layout #web"),
+            at("2025-07-31 11:00", "break"),
+            at("2025-07-31 12:00", "mails"),
+            at("2025-08-01 09:00", "start"),
+            at("2025-08-01 10:00", "fixes #web"),
+            at("2025-08-04 09:00", "start"),
+            at("2025-08-04 09:30", "fixes #web"),
+        ];
+
+        let day = DaySummary::compute(&entries[..4], &config).unwrap();
+        assert_eq!(Duration::minutes(150), day.work);
+        assert_eq!(Duration::minutes(90), day.billable);
+        assert_eq!(9000, day.revenue);
+        assert_eq!(60.0, day.utilization());
+        assert_eq!(0.0, DaySummary::default().utilization());
+
+        let weeks = period_summaries(&entries, &config, Period::Week).unwrap();
+        let weeks: Vec<_> = weeks
+            .iter()
+            .map(|(start, s)| (Period::Week.label(*start), s.billable.num_minutes()))
+            .collect();
+        assert_eq!(
+            vec![("2025-W31".to_string(), 150), ("2025-W32".to_string(), 30)],
+            weeks
+        );
+
+        let months = period_summaries(&entries, &config, Period::Month).unwrap();
+        assert_eq!(2, months.len());
+        assert_eq!("2025-07", Period::Month.label(months[0].0));
+        assert_eq!(Duration::minutes(90), months[1].1.work);
+        assert_eq!(9000, months[1].1.revenue);
+    }
+}
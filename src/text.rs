@@ -0,0 +1,312 @@
+use crate::model::{Change, Entry, NewEntry};
+use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::fmt::Write;
+
+const HEADER: &str = "\
+# Each line in the format 'HH:MM message' is an entry. Lines indented
+# below an entry are its long description. Removed lines are deleted,
+# new lines are added. Lines starting with '#' are ignored.
+";
+
+const INDENT: &str = "    ";
+
+/// An entry in the plain text representation of a day as produced by
+/// [`format_entries`], which only carries minute precision.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TextEntry {
+    pub time: NaiveTime,
+    pub message: String,
+    pub long: Option<String>,
+}
+
+impl From<&Entry> for TextEntry {
+    fn from(entry: &Entry) -> Self {
+        let time = entry.timestamp.time();
+        Self {
+            time: NaiveTime::from_hms_opt(time.hour(), time.minute(), 0).unwrap_or(time),
+            message: entry.message.clone(),
+            long: entry.long.clone(),
+        }
+    }
+}
+
+/// Formats the given entries into an editable plain text representation.
+pub fn format_entries(entries: &[Entry]) -> String {
+    let mut res = String::from(HEADER);
+    res.push('\n');
+
+    for e in entries {
+        // Writing to a String can not fail.
+        let _ = writeln!(res, "{} {}", e.timestamp.format("%H:%M"), e.message);
+        if let Some(ref long) = e.long {
+            for line in long.lines() {
+                match line.is_empty() {
+                    true => res.push('\n'),
+                    false => {
+                        let _ = writeln!(res, "{INDENT}{line}");
+                    }
+                }
+            }
+        }
+    }
+
+    res
+}
+
+/// Parses the plain text representation produced by [`format_entries`].
+pub fn parse_entries(text: &str) -> Result<Vec<TextEntry>> {
+    let mut entries: Vec<TextEntry> = vec![];
+    let mut long: Vec<&str> = vec![];
+
+    fn finish(entries: &mut [TextEntry], long: &mut Vec<&str>) {
+        if let Some(last) = entries.last_mut() {
+            let text = long.join("\n");
+            let text = text.trim_matches('\n');
+            last.long = (!text.is_empty()).then(|| text.to_string());
+        }
+        long.clear();
+    }
+
+    for (i, line) in text.lines().enumerate() {
+        let n = i + 1;
+
+        if line.trim().is_empty() {
+            if !entries.is_empty() {
+                long.push("");
+            }
+            continue;
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            if entries.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "line {n}: indented description without an entry"
+                ));
+            }
+            let line = line
+                .strip_prefix(INDENT)
+                .or_else(|| line.strip_prefix('\t'))
+                .unwrap_or_else(|| line.trim_start());
+            long.push(line);
+            continue;
+        }
+
+        if line.starts_with('#') {
+            continue;
+        }
+
+        finish(&mut entries, &mut long);
+
+        let (time, message) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let time = NaiveTime::parse_from_str(time, "%H:%M")
+            .map_err(|_| anyhow::anyhow!("line {n}: invalid time '{time}', expected HH:MM"))?;
+        let message = message.trim();
+        if message.is_empty() {
+            return Err(anyhow::anyhow!("line {n}: missing message"));
+        }
+
+        entries.push(TextEntry {
+            time,
+            message: message.to_string(),
+            long: None,
+        });
+    }
+
+    finish(&mut entries, &mut long);
+
+    Ok(entries)
+}
+
+/// Prepends the given error as comment to the text, replacing errors of
+/// previous runs, so that it is shown when re-opening the editor.
+pub fn with_error(text: &str, err: &anyhow::Error) -> String {
+    let text: String = text
+        .lines()
+        .filter(|l| !l.starts_with("# ERROR:"))
+        .map(|l| format!("{l}\n"))
+        .collect();
+    format!("# ERROR: {err}\n{text}")
+}
+
+/// Computes the changes required to turn the given old entries of `date`
+/// into the new entries.
+///
+/// Unchanged lines are matched via their longest common subsequence.
+/// Between those, changed lines are paired up and become updates,
+/// remaining old lines become deletes and remaining new lines become adds.
+pub fn diff(date: NaiveDate, old: &[Entry], new: &[TextEntry]) -> Vec<Change> {
+    let old_text: Vec<TextEntry> = old.iter().map(TextEntry::from).collect();
+    let (n, m) = (old.len(), new.len());
+
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = match old_text[i] == new[j] {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
+
+    let mut changes = vec![];
+    let mut removed = vec![];
+    let mut added = vec![];
+
+    let mut flush = |removed: &mut Vec<usize>, added: &mut Vec<usize>| {
+        // Prefer pairing lines that kept either their message or time
+        // before falling back to pairing them in order.
+        let mut pairs = vec![];
+        let mut unpaired = vec![];
+        for &j in added.iter() {
+            match removed.iter().position(|&i| {
+                old_text[i].message == new[j].message || old_text[i].time == new[j].time
+            }) {
+                Some(pos) => pairs.push((removed.remove(pos), j)),
+                None => unpaired.push(j),
+            }
+        }
+        let paired = removed.len().min(unpaired.len());
+        pairs.extend(removed.drain(..paired).zip(unpaired.drain(..paired)));
+
+        for (i, j) in pairs {
+            let timestamp = match old_text[i].time == new[j].time {
+                true => old[i].timestamp,
+                false => NaiveDateTime::new(date, new[j].time),
+            };
+            changes.push(Change::Update(Entry {
+                id: old[i].id,
+                timestamp,
+                message: new[j].message.clone(),
+                long: new[j].long.clone(),
+            }));
+        }
+        for i in removed.drain(..) {
+            changes.push(Change::Delete(old[i].id));
+        }
+        for j in unpaired {
+            changes.push(Change::Add(NewEntry {
+                timestamp: NaiveDateTime::new(date, new[j].time),
+                message: new[j].message.clone(),
+                long: new[j].long.clone(),
+            }));
+        }
+        added.clear();
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_text[i] == new[j] {
+            flush(&mut removed, &mut added);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            added.push(j);
+            j += 1;
+        } else {
+            removed.push(i);
+            i += 1;
+        }
+    }
+    flush(&mut removed, &mut added);
+
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TextEntry, diff, format_entries, parse_entries};
+    use crate::model::{Change, Entry, NewEntry};
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 7, 24).unwrap()
+    }
+
+    fn time(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M:%S").unwrap()
+    }
+
+    fn entry(id: u32, t: &str, message: &str, long: Option<&str>) -> Entry {
+        Entry {
+            id,
+            timestamp: NaiveDateTime::new(date(), time(t)),
+            message: message.to_string(),
+            long: long.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn format_and_parse() {
+        let entries = vec![
+            entry(1, "09:00:00", "start", None),
+            entry(2, "10:30:12", "meeting", Some("notes\n\nmore notes")),
+            entry(3, "12:00:00", "break", None),
+        ];
+
+        let text = format_entries(&entries);
+        assert!(text.contains("09:00 start\n10:30 meeting\n    notes\n\n    more notes\n12:00"));
+
+        let parsed = parse_entries(&text).unwrap();
+        let expected: Vec<TextEntry> = entries.iter().map(TextEntry::from).collect();
+        assert_eq!(expected, parsed);
+        assert_eq!(time("10:30:00"), parsed[1].time);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse_entries("  long without entry").is_err());
+        assert!(parse_entries("9 o'clock start").is_err());
+        assert!(parse_entries("09:00").is_err());
+        assert!(parse_entries("# comment\n\n09:00 start\n\t# long").is_ok());
+    }
+
+    #[test]
+    fn diff_entries() {
+        let old = vec![
+            entry(1, "09:00:30", "start", None),
+            entry(2, "10:00:00", "meeting", None),
+            entry(3, "11:00:00", "coding", None),
+            entry(4, "12:00:00", "break", None),
+        ];
+
+        let new = parse_entries(
+            "09:00 start\n\
+            10:15 meeting\n    \
+                notes\n\
+            12:00 break\n\
+            13:00 review\n",
+        )
+        .unwrap();
+
+        let changes = diff(date(), &old, &new);
+
+        assert_eq!(
+            vec![
+                Change::Update(Entry {
+                    long: Some("notes".to_string()),
+                    ..entry(2, "10:15:00", "meeting", None)
+                }),
+                Change::Delete(3),
+                Change::Add(NewEntry {
+                    timestamp: NaiveDateTime::new(date(), time("13:00:00")),
+                    message: "review".to_string(),
+                    long: None,
+                }),
+            ],
+            changes
+        );
+
+        let new = parse_entries("09:00 start\n10:00 meeting\n11:05 coding\n").unwrap();
+        assert_eq!(
+            vec![
+                Change::Update(entry(3, "11:05:00", "coding", None)),
+                Change::Delete(4),
+            ],
+            diff(date(), &old, &new)
+        );
+
+        let unchanged = parse_entries(&format_entries(&old)).unwrap();
+        assert!(diff(date(), &old, &unchanged).is_empty());
+    }
+}
@@ -0,0 +1,285 @@
+//! Printable monthly timesheets.
+
+use crate::config::Config;
+use crate::format::{escape, format_duration};
+use crate::model::Entry;
+use crate::summary::DaySummary;
+use crate::util::DateRange;
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use std::fmt::Write;
+
+mod pdf;
+
+/// The longest period a timesheet can cover, so that it fits on a page.
+pub const MAX_DAYS: i64 = 31;
+
+/// A row of a timesheet.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TimesheetDay {
+    pub date: NaiveDate,
+    pub start: Option<NaiveTime>,
+    pub end: Option<NaiveTime>,
+    pub summary: DaySummary,
+}
+
+/// The daily working times of a period, usually a month.
+#[derive(Clone, Debug)]
+pub struct Timesheet {
+    pub range: DateRange,
+    pub employee: Option<String>,
+    pub employer: Option<String>,
+    pub days: Vec<TimesheetDay>,
+    pub total: DaySummary,
+}
+
+impl Timesheet {
+    /// Collects the working times of each day of the range from the given
+    /// entries, ordered by their timestamp.
+    ///
+    /// A day starts with its first entry matching `start_regex` and ends with
+    /// its last entry matching `end_regex`, falling back to its first and last
+    /// entry.
+    pub fn new(range: DateRange, entries: &[Entry], config: &Config) -> Result<Self> {
+        let length = (range.end - range.start).num_days() + 1;
+        if length > MAX_DAYS {
+            return Err(anyhow::anyhow!(
+                "a timesheet covers at most {MAX_DAYS} days, but {range} has {length}"
+            ));
+        }
+
+        let mut days = vec![];
+        let mut total = DaySummary::default();
+        for date in range.start.iter_days().take(length as usize) {
+            let day: Vec<_> = entries
+                .iter()
+                .filter(|e| e.timestamp.date() == date)
+                .cloned()
+                .collect();
+            let summary = DaySummary::compute(&day, config)?;
+            total += summary;
+
+            let mut start = None;
+            for e in &day {
+                if e.message_matches(&config.start_regex)? {
+                    start = Some(e);
+                    break;
+                }
+            }
+            let mut end = None;
+            for e in day.iter().rev() {
+                if e.message_matches(&config.end_regex)? {
+                    end = Some(e);
+                    break;
+                }
+            }
+
+            days.push(TimesheetDay {
+                date,
+                start: start.or(day.first()).map(|e| e.timestamp.time()),
+                end: end.or(day.last()).map(|e| e.timestamp.time()),
+                summary,
+            });
+        }
+
+        Ok(Self {
+            range,
+            employee: config.employee.clone(),
+            employer: config.employer.clone(),
+            days,
+            total,
+        })
+    }
+
+    /// Returns the title of the timesheet, e.g. `Timesheet July 2025`.
+    pub fn title(&self) -> String {
+        let (start, end) = (self.range.start, self.range.end);
+        let whole_month = start.day() == 1
+            && end.month() == start.month()
+            && end.succ_opt().is_some_and(|d| d.day() == 1);
+        match whole_month {
+            true => format!("Timesheet {}", start.format("%B %Y")),
+            false => format!("Timesheet {start} to {end}"),
+        }
+    }
+
+    /// Renders the timesheet as a self-contained HTML document.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let title = escape(&self.title());
+        // Writing to a string can not fail.
+        let _ = write!(
+            html,
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+@page {{ size: A4; margin: 20mm; }}
+body {{ font-family: Helvetica, Arial, sans-serif; font-size: 10pt; color: #222; max-width: 180mm; margin: 0 auto; }}
+h1 {{ font-size: 16pt; margin-bottom: 4pt; }}
+.parties {{ display: flex; gap: 24pt; margin-bottom: 12pt; }}
+.parties div span {{ display: block; color: #666; font-size: 8pt; text-transform: uppercase; }}
+table {{ width: 100%; border-collapse: collapse; }}
+th, td {{ padding: 3pt 6pt; border-bottom: 1px solid #ddd; text-align: left; }}
+th {{ background: #d9e1f2; border-bottom: 1px solid #888; }}
+td.time {{ font-variant-numeric: tabular-nums; }}
+tr.weekend td {{ background: #f4f4f4; color: #888; }}
+tfoot td {{ font-weight: bold; border-top: 2px solid #888; border-bottom: none; }}
+.signatures {{ display: flex; gap: 48pt; margin-top: 48pt; }}
+.signatures div {{ flex: 1; border-top: 1px solid #222; padding-top: 4pt; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<div class="parties">
+<div><span>Employee</span>{}</div>
+<div><span>Employer</span>{}</div>
+</div>
+<table>
+<thead>
+<tr><th>Date</th><th>Day</th><th>Start</th><th>End</th><th>Break</th><th>Total</th></tr>
+</thead>
+<tbody>
+"#,
+            escape(self.employee.as_deref().unwrap_or_default()),
+            escape(self.employer.as_deref().unwrap_or_default()),
+        );
+
+        for day in &self.days {
+            let class = match day.date.weekday().number_from_monday() > 5 {
+                true => r#" class="weekend""#,
+                false => "",
+            };
+            let _ = writeln!(
+                html,
+                r#"<tr{class}><td>{}</td><td>{}</td><td class="time">{}</td><td class="time">{}</td><td class="time">{}</td><td class="time">{}</td></tr>"#,
+                day.date.format("%Y-%m-%d"),
+                day.date.format("%A"),
+                format_time(day.start),
+                format_time(day.end),
+                day.pause_formatted(),
+                day.work_formatted(),
+            );
+        }
+
+        let _ = write!(
+            html,
+            r#"</tbody>
+<tfoot>
+<tr><td colspan="4">Total</td><td class="time">{}</td><td class="time">{}</td></tr>
+</tfoot>
+</table>
+<div class="signatures">
+<div>Date, signature employee</div>
+<div>Date, signature employer</div>
+</div>
+</body>
+</html>
+"#,
+            format_duration(self.total.pause),
+            format_duration(self.total.work),
+        );
+        html
+    }
+
+    /// Renders the timesheet as a single page PDF document.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        pdf::render(self)
+    }
+}
+
+impl TimesheetDay {
+    fn is_tracked(&self) -> bool {
+        self.start.is_some()
+    }
+
+    fn pause_formatted(&self) -> String {
+        match self.is_tracked() {
+            true => format_duration(self.summary.pause),
+            false => String::new(),
+        }
+    }
+
+    fn work_formatted(&self) -> String {
+        match self.is_tracked() {
+            true => format_duration(self.summary.work),
+            false => String::new(),
+        }
+    }
+}
+
+fn format_time(time: Option<NaiveTime>) -> String {
+    time.map(|t| t.format("%H:%M").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::Timesheet;
+    use crate::config::Config;
+    use crate::model::Entry;
+    use crate::util::DateRange;
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use figment::Figment;
+    use figment::providers::{Format, Toml};
+
+    fn entry(date_time: &str, message: &str) -> Entry {
+        Entry {
+            id: 0,
+            timestamp: NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").unwrap(),
+            message: message.to_string(),
+            long: None,
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn days_and_totals() {
+        let config: Config = Figment::new()
+            .merge(Toml::string(r#"employee = "Jo <Doe>""#))
+            .extract()
+            .unwrap();
+        let entries = vec![
+            entry("2025-07-01 07:55", "mails"),
+            entry("2025-07-01 08:00", "start"),
+            entry("2025-07-01 12:00", "coding"),
+            entry("2025-07-01 12:30", "break"),
+            entry("2025-07-01 16:30", "end"),
+            entry("2025-07-02 09:00", "coding"),
+            entry("2025-07-02 10:00", "review"),
+        ];
+        let range = DateRange::new(date("2025-07-01"), date("2025-07-31")).unwrap();
+        let sheet = Timesheet::new(range, &entries, &config).unwrap();
+
+        assert_eq!("Timesheet July 2025", sheet.title());
+        assert_eq!(31, sheet.days.len());
+        let first = &sheet.days[0];
+        assert_eq!(Some("08:00".parse().unwrap()), first.start);
+        assert_eq!(Some("16:30".parse().unwrap()), first.end);
+        assert_eq!(Duration::minutes(30), first.summary.pause);
+        let second = &sheet.days[1];
+        assert_eq!(Some("09:00".parse().unwrap()), second.start);
+        assert_eq!(Some("10:00".parse().unwrap()), second.end);
+        assert_eq!(None, sheet.days[2].start);
+        assert_eq!(Duration::minutes(545), sheet.total.work);
+
+        let html = sheet.to_html();
+        assert!(html.contains("Jo &lt;Doe&gt;"));
+        assert!(html.contains(
+            "<td>2025-07-01</td><td>Tuesday</td><td class=\"time\">08:00</td>\
+            <td class=\"time\">16:30</td><td class=\"time\">0:30</td><td class=\"time\">8:05</td>"
+        ));
+        assert!(html.contains("<tr class=\"weekend\"><td>2025-07-05</td>"));
+        assert!(html.contains("<td class=\"time\">0:30</td><td class=\"time\">9:05</td>"));
+
+        assert!(sheet.to_pdf().starts_with(b"%PDF"));
+
+        let too_long = DateRange::new(date("2025-07-01"), date("2025-08-01")).unwrap();
+        assert!(Timesheet::new(too_long, &entries, &config).is_err());
+    }
+}
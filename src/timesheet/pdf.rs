@@ -0,0 +1,103 @@
+use super::{Timesheet, format_time};
+use crate::format::format_duration;
+use crate::pdf::{BOLD, MARGIN, PAGE_HEIGHT, PAGE_WIDTH, REGULAR, document, line, text};
+use chrono::Datelike;
+use pdf_writer::Content;
+
+const ROW_HEIGHT: f32 = 16.0;
+
+/// X positions of the table columns.
+const COLUMNS: [f32; 6] = [MARGIN, 130.0, 230.0, 300.0, 370.0, 440.0];
+
+/// Renders the timesheet on a single A4 page.
+pub(super) fn render(sheet: &Timesheet) -> Vec<u8> {
+    let mut content = Content::new();
+    let mut y = PAGE_HEIGHT - MARGIN;
+
+    text(&mut content, BOLD, 16.0, MARGIN, y, &sheet.title());
+    y -= 28.0;
+    let parties = [
+        (MARGIN, "EMPLOYEE", &sheet.employee),
+        (300.0, "EMPLOYER", &sheet.employer),
+    ];
+    for (x, label, name) in parties {
+        text(&mut content, REGULAR, 7.0, x, y, label);
+        text(
+            &mut content,
+            REGULAR,
+            10.0,
+            x,
+            y - 12.0,
+            name.as_deref().unwrap_or_default(),
+        );
+    }
+    y -= 36.0;
+
+    let header = ["Date", "Day", "Start", "End", "Break", "Total"];
+    for (x, label) in COLUMNS.into_iter().zip(header) {
+        text(&mut content, BOLD, 10.0, x, y, label);
+    }
+    line(&mut content, y - 5.0, 0.8);
+    y -= ROW_HEIGHT;
+
+    for day in &sheet.days {
+        if day.date.weekday().number_from_monday() > 5 {
+            content.set_fill_gray(0.95);
+            content.rect(
+                MARGIN - 2.0,
+                y - 5.0,
+                PAGE_WIDTH - 2.0 * MARGIN + 4.0,
+                ROW_HEIGHT,
+            );
+            content.fill_nonzero();
+            content.set_fill_gray(0.0);
+        }
+        let cells = [
+            day.date.format("%Y-%m-%d").to_string(),
+            day.date.format("%A").to_string(),
+            format_time(day.start),
+            format_time(day.end),
+            day.pause_formatted(),
+            day.work_formatted(),
+        ];
+        for (x, cell) in COLUMNS.into_iter().zip(cells) {
+            text(&mut content, REGULAR, 10.0, x, y, &cell);
+        }
+        y -= ROW_HEIGHT;
+    }
+
+    line(&mut content, y + ROW_HEIGHT - 5.0, 1.2);
+    text(&mut content, BOLD, 10.0, COLUMNS[0], y, "Total");
+    text(
+        &mut content,
+        BOLD,
+        10.0,
+        COLUMNS[4],
+        y,
+        &format_duration(sheet.total.pause),
+    );
+    text(
+        &mut content,
+        BOLD,
+        10.0,
+        COLUMNS[5],
+        y,
+        &format_duration(sheet.total.work),
+    );
+
+    let y = MARGIN + 20.0;
+    let half = (PAGE_WIDTH - 2.0 * MARGIN - 48.0) / 2.0;
+    let signatures = [
+        (MARGIN, "Date, signature employee"),
+        (MARGIN + half + 48.0, "Date, signature employer"),
+    ];
+    for (x, label) in signatures {
+        content.set_line_width(0.8);
+        content.move_to(x, y);
+        content.line_to(x + half, y);
+        content.stroke();
+        text(&mut content, REGULAR, 8.0, x, y - 12.0, label);
+    }
+
+    document(vec![content])
+}
@@ -1,11 +1,13 @@
-use crate::config::Config;
+use crate::clock;
+use crate::config::{Config, EntryFilter};
 use crate::model::Entry;
 use anyhow::Result;
-use chrono::{Datelike, Duration, Local, NaiveDate};
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
 use fancy_duration::{AsFancyDuration, AsTimes};
 use inquire::DateSelect;
 use regex::Regex;
 use std::fmt;
+use std::io::{self, IsTerminal};
 use std::str::FromStr;
 use yansi::{Paint, Style};
 
@@ -78,6 +80,26 @@ impl Entry {
         Ok(Regex::new(rx)?.is_match(&self.message))
     }
 
+    /// Returns the words of the message starting with `#`, without it.
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.message
+            .split_whitespace()
+            .filter_map(|w| w.strip_prefix('#'))
+            .filter(|t| !t.is_empty())
+    }
+
+    pub fn matches(&self, filter: &EntryFilter) -> Result<bool> {
+        if let Some(ref tag) = filter.tag
+            && !self.tags().any(|t| t.eq_ignore_ascii_case(tag))
+        {
+            return Ok(false);
+        }
+        match filter.message {
+            Some(ref rx) => self.message_matches(rx),
+            None => Ok(true),
+        }
+    }
+
     fn style_message<'a>(&'a self, config: &Config) -> Result<Box<dyn fmt::Display + 'a>> {
         Ok(if self.message_matches(&config.start_regex)? {
             Box::new(self.message.paint(STYLE_START))
@@ -133,45 +155,227 @@ impl FromStr for Parsable<NaiveDate> {
     /// | `-<n_days>` | Today minus <n_days>. | `-2` | `2025-07-22` |
     /// | `y[y...]` | Today minus count of 'y'. | `yyy` | `2025-07-21` |
     fn from_str(date: &str) -> std::result::Result<Self, Self::Err> {
-        let today = Local::now().date_naive();
-
-        let y_count = date.chars().take_while(|&c| c == 'y' || c == 'Y').count();
-        if y_count > 0 {
-            if y_count != date.len() {
-                return Err(anyhow::anyhow!("additional characters after 'y'"));
-            }
-            return Ok(Self(today - Duration::days(y_count as i64)));
+        parse_date(date, clock::today()).map(Self)
+    }
+}
+
+fn parse_date(date: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let y_count = date.chars().take_while(|&c| c == 'y' || c == 'Y').count();
+    if y_count > 0 {
+        if y_count != date.len() {
+            return Err(anyhow::anyhow!("additional characters after 'y'"));
         }
+        return Ok(today - Duration::days(y_count as i64));
+    }
+
+    if let Some(days_str) = date.strip_prefix('-') {
+        let days = days_str.parse()?;
+        return Ok(today - Duration::days(days));
+    }
+
+    let delims = date.chars().filter(|&c| c == '-').count();
 
-        if let Some(days_str) = date.strip_prefix('-') {
-            let days = days_str.parse()?;
-            return Ok(Self(today - Duration::days(days)));
+    let year = today.year();
+    let month = today.month0() + 1;
+
+    let date = match delims {
+        0 => format!("{year}-{month}-{date}"),
+        1 => format!("{year}-{date}"),
+        2 => date.to_string(),
+        _ => return Err(anyhow::anyhow!("invalid date format")),
+    };
+
+    Ok(NaiveDate::parse_from_str(&date, "%Y-%m-%d")?)
+}
+
+/// An inclusive range of days.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Result<Self> {
+        if start > end {
+            return Err(anyhow::anyhow!(
+                "start date {start} is after end date {end}"
+            ));
         }
+        Ok(Self { start, end })
+    }
 
-        let delims = date.chars().filter(|&c| c == '-').count();
+    pub fn day(date: NaiveDate) -> Self {
+        Self {
+            start: date,
+            end: date,
+        }
+    }
 
-        let year = today.year();
-        let month = today.month0() + 1;
+    pub fn is_single_day(&self) -> bool {
+        self.start == self.end
+    }
 
-        let date = match delims {
-            0 => format!("{year}-{month}-{date}"),
-            1 => format!("{year}-{date}"),
-            2 => date.to_string(),
-            _ => return Err(anyhow::anyhow!("invalid date format")),
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+
+    fn month(year: i32, month: u32) -> Result<Self> {
+        let start = NaiveDate::from_ymd_opt(year, month, 1)
+            .ok_or_else(|| anyhow::anyhow!("invalid month {year}-{month}"))?;
+        let end = start + Months::new(1) - Duration::days(1);
+        Ok(Self { start, end })
+    }
+
+    fn quarter(year: i32, quarter: u32) -> Result<Self> {
+        if !(1..=4).contains(&quarter) {
+            return Err(anyhow::anyhow!("invalid quarter {quarter}"));
+        }
+        let first = Self::month(year, quarter * 3 - 2)?;
+        let last = Self::month(year, quarter * 3)?;
+        Ok(Self {
+            start: first.start,
+            end: last.end,
+        })
+    }
+
+    fn year(year: i32) -> Result<Self> {
+        let start = NaiveDate::from_ymd_opt(year, 1, 1)
+            .ok_or_else(|| anyhow::anyhow!("invalid year {year}"))?;
+        let end = start + Months::new(12) - Duration::days(1);
+        Ok(Self { start, end })
+    }
+
+    fn iso_week(year: i32, week: u32) -> Result<Self> {
+        let start = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+            .ok_or_else(|| anyhow::anyhow!("invalid week {year}-W{week}"))?;
+        Ok(Self {
+            start,
+            end: start + Duration::days(6),
+        })
+    }
+}
+
+impl fmt::Display for DateRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.is_single_day() {
+            true => write!(f, "{}", self.start),
+            false => write!(f, "{}..{}", self.start, self.end),
+        }
+    }
+}
+
+impl FromStr for Parsable<DateRange> {
+    type Err = anyhow::Error;
+
+    /// Parses a given date range string as following:
+    ///
+    /// The following table assumes the current date as `2025-07-24` for the
+    /// examples. All single dates accepted by [`Parsable<NaiveDate>`] are
+    /// accepted as well and result in a range of that one day.
+    ///
+    /// | Format | Description | Example | Resulting Range |
+    /// |--------|-------------|---------|-----------------|
+    /// | `<from>..<to>` | Range between two dates or ranges. | `2025-07-01..2025-07-03` | `2025-07-01..2025-07-03` |
+    /// | `<from>..` | Range from a date until today. | `-7..` | `2025-07-17..2025-07-24` |
+    /// | `[<yyyy>-]W<ww>` | ISO week; year defaults to the current year. | `W30` | `2025-07-21..2025-07-27` |
+    /// | `<yyyy>-<mm>` | Whole month. | `2025-07` | `2025-07-01..2025-07-31` |
+    /// | `[<yyyy>-]Q<n>` | Quarter; year defaults to the current year. | `q3` | `2025-07-01..2025-09-30` |
+    /// | `today`, `yesterday` | Single relative day. | `yesterday` | `2025-07-23` |
+    /// | `this-week`, `last-week` | Current or previous week. | `last-week` | `2025-07-14..2025-07-20` |
+    /// | `this-month`, `last-month` | Current or previous month. | `last-month` | `2025-06-01..2025-06-30` |
+    /// | `this-year`, `last-year` | Current or previous year. | `this-year` | `2025-01-01..2025-12-31` |
+    fn from_str(range: &str) -> std::result::Result<Self, Self::Err> {
+        parse_date_range(range, clock::today()).map(Self)
+    }
+}
+
+fn parse_date_range(range: &str, today: NaiveDate) -> Result<DateRange> {
+    if let Some((from, to)) = range.split_once("..") {
+        if from.is_empty() {
+            return Err(anyhow::anyhow!("date range requires a start date"));
+        }
+        let start = parse_date_range(from, today)?.start;
+        let end = match to.is_empty() {
+            true => today,
+            false => parse_date_range(to, today)?.end,
         };
+        return DateRange::new(start, end);
+    }
+
+    let range = range.to_lowercase();
+
+    match range.as_str() {
+        "today" => return Ok(DateRange::day(today)),
+        "yesterday" => return Ok(DateRange::day(today - Duration::days(1))),
+        "this-week" => {
+            return DateRange::iso_week(today.iso_week().year(), today.iso_week().week());
+        }
+        "last-week" => {
+            let last = (today - Duration::days(7)).iso_week();
+            return DateRange::iso_week(last.year(), last.week());
+        }
+        "this-month" => return DateRange::month(today.year(), today.month()),
+        "last-month" => {
+            let last = today - Months::new(1);
+            return DateRange::month(last.year(), last.month());
+        }
+        "this-year" => return DateRange::year(today.year()),
+        "last-year" => return DateRange::year(today.year() - 1),
+        _ => {}
+    }
+
+    let (year, rest) = match range.split_once('-') {
+        Some((year, rest)) if year.len() == 4 => (Some(year.parse::<i32>()?), rest),
+        _ => (None, range.as_str()),
+    };
+
+    if let Some(week) = rest.strip_prefix('w') {
+        let year = year.unwrap_or(today.iso_week().year());
+        return DateRange::iso_week(year, week.parse()?);
+    }
 
-        Ok(Self(NaiveDate::parse_from_str(&date, "%Y-%m-%d")?))
+    if let Some(quarter) = rest.strip_prefix('q') {
+        return DateRange::quarter(year.unwrap_or(today.year()), quarter.parse()?);
+    }
+
+    if let Some(year) = year
+        && !rest.contains('-')
+    {
+        return DateRange::month(year, rest.parse()?);
     }
+
+    parse_date(&range, today).map(DateRange::day)
 }
 
 pub fn select_date() -> Result<NaiveDate> {
+    ensure_interactive("pass a date instead of using --select")?;
     let date = DateSelect::new("Select Date").prompt()?;
     Ok(date)
 }
 
+/// Returns an error containing the given hint when stdin is not attached
+/// to a terminal, so that interactive prompts can not be shown.
+pub fn ensure_interactive(hint: &str) -> Result<()> {
+    if !io::stdin().is_terminal() {
+        return Err(anyhow::anyhow!("stdin is not a terminal; {hint}"));
+    }
+    Ok(())
+}
+
+/// Returns the entry at the given index as displayed by `view`, starting
+/// at 1. The entries must be sorted by timestamp.
+pub fn entry_by_index(entries: &[Entry], index: usize) -> Result<&Entry> {
+    index
+        .checked_sub(1)
+        .and_then(|i| entries.get(i))
+        .ok_or_else(|| anyhow::anyhow!("no entry with index {index}"))
+}
+
 pub struct FormatableEntry<'c, 'e> {
     pub config: &'c Config,
     pub long: bool,
+    pub date: bool,
     pub entry: &'e Entry,
 }
 
@@ -181,12 +385,22 @@ impl<'c, 'e> FormatableEntry<'c, 'e> {
             entry,
             config,
             long,
+            date: false,
         }
     }
+
+    /// Prefix the formatted entry with its date.
+    pub fn with_date(mut self, date: bool) -> Self {
+        self.date = date;
+        self
+    }
 }
 
 impl fmt::Display for FormatableEntry<'_, '_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.date {
+            write!(f, "{} ", self.entry.timestamp.format("%Y-%m-%d").dim())?;
+        }
         self.entry
             .format(f, self.config, self.long, None::<chrono::TimeDelta>)
             .map_err(|_| fmt::Error)
@@ -195,8 +409,8 @@ impl fmt::Display for FormatableEntry<'_, '_> {
 
 #[cfg(test)]
 mod test {
-    use crate::util::Parsable;
-    use chrono::{Datelike, Duration, Local, NaiveDate};
+    use crate::util::DateRange;
+    use chrono::NaiveDate;
 
     #[test]
     fn format_long() {
@@ -209,55 +423,78 @@ mod test {
 
     #[test]
     fn parse_date() {
-        fn get_date<S: AsRef<str>>(date: S) -> NaiveDate {
-            NaiveDate::parse_from_str(date.as_ref(), "%Y-%m-%d").unwrap()
+        fn date(s: &str) -> NaiveDate {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
         }
 
-        fn get_date_parts(date: NaiveDate) -> (u32, u32, u32) {
-            let (_, year) = date.year_ce();
-            let month = date.month0() + 1;
-            let day = date.day0() + 1;
-            (year, month, day)
+        fn parse(s: &str) -> NaiveDate {
+            super::parse_date(s, date("2025-07-24")).unwrap()
         }
 
-        let (year, month, _) = get_date_parts(Local::now().date_naive());
-        assert_eq!(
-            get_date("2025-07-24"),
-            "2025-07-24".parse::<Parsable<NaiveDate>>().unwrap().0
-        );
-        assert_eq!(
-            get_date(format!("{year}-07-24",)),
-            "07-24".parse::<Parsable<NaiveDate>>().unwrap().0
-        );
-        assert_eq!(
-            get_date(format!("{year}-{month}-24",)),
-            "24".parse::<Parsable<NaiveDate>>().unwrap().0
-        );
+        assert_eq!(date("2024-02-29"), parse("2024-02-29"));
+        assert_eq!(date("2025-03-24"), parse("03-24"));
+        assert_eq!(date("2025-07-03"), parse("03"));
 
-        assert_eq!(
-            (Local::now() - Duration::days(1)).date_naive(),
-            "-1".parse::<Parsable<NaiveDate>>().unwrap().0
-        );
-        assert_eq!(
-            (Local::now() - Duration::days(2)).date_naive(),
-            "-2".parse::<Parsable<NaiveDate>>().unwrap().0
-        );
-        assert_eq!(
-            (Local::now() - Duration::days(69)).date_naive(),
-            "-69".parse::<Parsable<NaiveDate>>().unwrap().0
-        );
+        assert_eq!(date("2025-07-23"), parse("-1"));
+        assert_eq!(date("2025-07-22"), parse("-2"));
+        assert_eq!(date("2025-05-16"), parse("-69"));
+
+        assert_eq!(date("2025-07-23"), parse("y"));
+        assert_eq!(date("2025-07-22"), parse("yY"));
+        assert_eq!(date("2025-07-18"), parse("YYYYYY"));
+    }
+
+    #[test]
+    fn parse_date_range() {
+        fn date(s: &str) -> NaiveDate {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+        }
+
+        fn range(from: &str, to: &str) -> DateRange {
+            DateRange::new(date(from), date(to)).unwrap()
+        }
+
+        fn parse(s: &str) -> DateRange {
+            super::parse_date_range(s, date("2025-07-24")).unwrap()
+        }
 
+        assert_eq!(range("2025-07-24", "2025-07-24"), parse("2025-07-24"));
+        assert_eq!(range("2025-07-22", "2025-07-22"), parse("-2"));
+        assert_eq!(range("2025-07-23", "2025-07-23"), parse("yesterday"));
         assert_eq!(
-            (Local::now() - Duration::days(1)).date_naive(),
-            "y".parse::<Parsable<NaiveDate>>().unwrap().0
+            range("2025-07-01", "2025-07-31"),
+            parse("2025-07-01..2025-07-31")
         );
+        assert_eq!(range("2025-07-17", "2025-07-24"), parse("-7.."));
+        assert_eq!(range("2025-07-20", "2025-07-24"), parse("20.."));
+        assert_eq!(range("2025-07-21", "2025-07-27"), parse("W30"));
+        assert_eq!(range("2024-12-30", "2025-01-05"), parse("2025-w01"));
+        assert_eq!(range("2025-07-01", "2025-07-31"), parse("2025-07"));
+        assert_eq!(range("2024-02-01", "2024-02-29"), parse("2024-02"));
+        assert_eq!(range("2025-07-01", "2025-09-30"), parse("q3"));
+        assert_eq!(range("2024-10-01", "2024-12-31"), parse("2024-Q4"));
+        assert_eq!(range("2025-07-21", "2025-07-27"), parse("this-week"));
+        assert_eq!(range("2025-07-14", "2025-07-20"), parse("last-week"));
+        assert_eq!(range("2025-07-01", "2025-07-31"), parse("this-month"));
+        assert_eq!(range("2025-06-01", "2025-06-30"), parse("last-month"));
+        assert_eq!(range("2024-01-01", "2024-12-31"), parse("last-year"));
+        assert_eq!(range("2025-06-01", "2025-08-31"), parse("2025-06..2025-08"));
+        assert_eq!(range("2025-07-14", "2025-07-27"), parse("W29..W30"));
+
+        let today = date("2025-07-24");
+        assert!(super::parse_date_range("..2025-07-01", today).is_err());
+        assert!(super::parse_date_range("2025-07-31..2025-07-01", today).is_err());
+        assert!(super::parse_date_range("q5", today).is_err());
+        assert!(super::parse_date_range("W54", today).is_err());
+        // A bare week belongs to the ISO week year of today.
         assert_eq!(
-            (Local::now() - Duration::days(2)).date_naive(),
-            "yY".parse::<Parsable<NaiveDate>>().unwrap().0
+            range("2025-12-29", "2026-01-04"),
+            super::parse_date_range("W01", date("2025-12-30")).unwrap()
         );
         assert_eq!(
-            (Local::now() - Duration::days(6)).date_naive(),
-            "YYYYYY".parse::<Parsable<NaiveDate>>().unwrap().0
+            range("2020-12-28", "2021-01-03"),
+            super::parse_date_range("W53", date("2021-01-02")).unwrap()
         );
+        assert!(super::parse_date_range("2025-13", today).is_err());
     }
 }
@@ -0,0 +1,473 @@
+mod common;
+
+use common::Tracker;
+
+#[test]
+fn add_and_view_at_fixed_time() {
+    let mut tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T12:00").ok(&["add", "break"]);
+    tracker.at("2025-07-24T12:30").ok(&["add", "work"]);
+    tracker.at("2025-07-24T17:00").ok(&["add", "end"]);
+
+    let out = tracker.ok(&["view"]);
+    assert!(out.contains("09:00 : start"), "{out}");
+    assert!(out.contains("12:30 : work (30m)"), "{out}");
+    assert!(out.contains("17:00 : end (4h 30m)"), "{out}");
+    assert!(out.contains("5h (3h pause)"), "{out}");
+
+    // Relative dates are resolved against the fixed clock as well.
+    tracker.at("2025-07-25T08:00");
+    assert!(tracker.ok(&["view", "yesterday"]).contains("09:00 : start"));
+    assert!(!tracker.ok(&["view"]).contains("start"));
+    assert!(tracker.storage_dir().join("db.sqlite").is_file());
+}
+
+#[test]
+fn delete_and_undo() {
+    let tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+    tracker.ok(&["add", "--time", "10:00", "work"]);
+
+    tracker.ok(&["delete", "-n", "1"]);
+    assert!(!tracker.ok(&["view"]).contains("start"));
+
+    tracker.ok(&["undo"]);
+    let out = tracker.ok(&["view"]);
+    assert!(out.contains("09:00 : start"), "{out}");
+    assert!(out.contains("10:00 : work"), "{out}");
+
+    // Both the deletion and undoing it are backed up before.
+    let backups = tracker.ok(&["backup", "--list"]);
+    assert!(backups.contains("delete"), "{backups}");
+    assert!(backups.contains("undo"), "{backups}");
+
+    // The date of the list is positional, as for `edit`.
+    tracker.ok(&["delete", "2025-07-24", "-n", "2"]);
+    assert!(!tracker.ok(&["view"]).contains("work"));
+}
+
+#[test]
+fn trash_restore_and_purge_are_logged() {
+    let tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+    tracker.ok(&["add", "--time", "10:00", "work"]);
+    tracker.ok(&["delete", "-n", "2"]);
+
+    tracker.ok(&["trash", "restore", "2"]);
+    assert!(tracker.ok(&["view"]).contains("work"));
+    let out = tracker.ok(&["undo"]);
+    assert!(out.contains("Undone: restore 'work'"), "{out}");
+    assert!(tracker.ok(&["trash"]).contains("work"));
+
+    // Undoing an add moves the entry to the trash instead of removing it.
+    let out = tracker.ok(&["undo"]);
+    assert!(out.contains("Undone: delete 'work'"), "{out}");
+    tracker.ok(&["undo"]);
+    assert!(tracker.ok(&["trash"]).contains("work"));
+
+    // Purged entries are gone from the log, so only `add start` is left.
+    tracker.ok(&["trash", "purge", "--all"]);
+    let out = tracker.ok(&["redo"]);
+    assert!(out.contains("nothing to redo"), "{out}");
+    let out = tracker.ok(&["undo", "5"]);
+    assert!(out.contains("Undone: add 'start'"), "{out}");
+    assert!(!out.contains("work"), "{out}");
+}
+
+#[test]
+fn purged_ids_are_not_reused() {
+    let mut tracker = Tracker::with_config(
+        "[[clients]]\nname = \"Acme\"\ntag = \"acme\"\nrate = 60\nincrement = \"15m\"",
+    );
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T10:00").ok(&["add", "login #acme"]);
+    let html = tracker.storage_dir().join("invoice.html");
+    let html = html.to_str().unwrap();
+    tracker.ok(&["invoice", "acme", "-d", "2025-07", "-o", html]);
+
+    tracker.ok(&["delete", "-n", "2"]);
+    tracker.ok(&["trash", "purge", "--all"]);
+    tracker.at("2025-07-24T12:00").ok(&["add", "call #acme"]);
+
+    let ids = tracker.ok(&["view", "--ids"]);
+    assert!(ids.contains("#3"), "{ids}");
+    let history = tracker.ok(&["history", "--id", "3"]);
+    assert!(!history.contains("login"), "{history}");
+    let out = tracker.ok(&["invoice", "acme", "-d", "2025-07", "-o", html]);
+    assert!(out.contains("Issued invoice 2025-0002"), "{out}");
+}
+
+#[test]
+fn trash_retention_purges_automatically() {
+    let mut tracker = Tracker::with_config("trash_retention = \"1d\"");
+    tracker.ok(&["add", "start"]);
+    tracker.ok(&["delete", "-n", "1"]);
+    assert!(
+        tracker
+            .at("2025-07-25T08:00")
+            .ok(&["trash"])
+            .contains("start")
+    );
+
+    // Only commands which write purge expired entries, dry runs do not.
+    tracker.at("2025-07-25T10:00");
+    assert!(tracker.ok(&["trash"]).contains("start"));
+    tracker.ok(&["--dry-run", "add", "start"]);
+    assert!(tracker.ok(&["trash"]).contains("start"));
+    tracker.ok(&["add", "start"]);
+    let out = tracker.ok(&["trash"]);
+    assert!(out.contains("The trash is empty."), "{out}");
+}
+
+#[test]
+fn dry_run_takes_no_backup() {
+    let tracker = Tracker::with_config("daily_backup = true");
+    let backups = tracker.storage_dir().join("backups");
+    tracker.ok(&["--dry-run", "add", "start"]);
+    assert!(!backups.exists() || std::fs::read_dir(&backups).unwrap().next().is_none());
+
+    tracker.ok(&["view"]);
+    assert!(tracker.ok(&["backup", "--list"]).contains("daily"));
+}
+
+#[test]
+fn locked_periods_require_force() {
+    let tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+    tracker.ok(&["lock", "2025-07"]);
+
+    let err = tracker.err(&["add", "work"]);
+    assert!(err.contains("2025-07-24 is locked"), "{err}");
+    assert!(
+        tracker
+            .err(&["delete", "--index", "1"])
+            .contains("is locked")
+    );
+
+    tracker.ok(&["--force", "add", "work"]);
+    assert!(tracker.ok(&["history"]).contains("(forced)"));
+}
+
+#[test]
+fn dry_run_does_not_save() {
+    let tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+
+    let out = tracker.ok(&["--dry-run", "add", "--time", "10:00", "work"]);
+    assert!(out.contains("Dry run"), "{out}");
+    assert!(!tracker.ok(&["view"]).contains("work"));
+}
+
+#[test]
+fn dry_run_keeps_locks_and_undo_log() {
+    let tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+    tracker.ok(&["add", "--time", "10:00", "work"]);
+
+    let out = tracker.ok(&["undo", "--dry-run"]);
+    assert!(out.contains("Undone: add"), "{out}");
+    assert!(out.contains("Dry run"), "{out}");
+    assert!(tracker.ok(&["view"]).contains("work"));
+
+    tracker.ok(&["lock", "2025-07"]);
+    let err = tracker.err(&["--dry-run", "add", "lunch"]);
+    assert!(err.contains("2025-07-24 is locked"), "{err}");
+    tracker.ok(&["--dry-run", "--force", "add", "lunch"]);
+
+    let err = tracker.err(&["--dry-run", "history"]);
+    assert!(
+        err.contains("not available in the in-memory storage"),
+        "{err}"
+    );
+    let err = tracker.err(&["--dry-run", "db", "vacuum"]);
+    assert!(err.contains("can not be run with --dry-run"), "{err}");
+}
+
+#[test]
+fn invalid_clock_is_rejected() {
+    let mut tracker = Tracker::new();
+    let err = tracker.at("tomorrow").err(&["view"]);
+    assert!(err.contains("invalid TRACKER_NOW"), "{err}");
+}
+
+#[test]
+fn now_argument_overrides_env() {
+    let tracker = Tracker::with_config("round_steps = \"15m\"");
+    tracker.ok(&["--now", "2025-07-20T08:05", "add", "start"]);
+    tracker.ok(&["add", "--now=2025-07-20T12:08", "end"]);
+
+    let out = tracker.ok(&["view", "2025-07-20"]);
+    // Timestamps are kept exact, `round_steps` only rounds billed time.
+    assert!(out.contains("08:05 : start"), "{out}");
+    assert!(out.contains("12:08 : end (4h 3m)"), "{out}");
+    assert!(tracker.ok(&["view"]).contains("no entries"));
+    // Relative dates are resolved against the given time as well.
+    let out = tracker.ok(&["view", "y", "--now", "2025-07-21T09:00"]);
+    assert!(out.contains("08:05 : start"), "{out}");
+    // Open ranges end at the given time, even if it is after the real one.
+    let out = tracker.ok(&["--now", "2099-06-01T09:00", "view", "2099-01-01.."]);
+    assert!(out.contains("no entries"), "{out}");
+}
+
+#[test]
+fn export_ics() {
+    let mut tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T11:00").ok(&["add", "coding"]);
+    tracker.at("2025-07-24T11:30").ok(&["add", "break"]);
+
+    let out = tracker.ok(&["export", "--format", "ics"]);
+    assert_eq!(2, out.matches("BEGIN:VEVENT").count());
+    assert!(out.contains("DTSTART:20250724T090000\r\nDTEND:20250724T110000\r\nSUMMARY:coding"));
+
+    let path = tracker.storage_dir().join("week.ics");
+    let out = tracker.ok(&[
+        "export",
+        "this-week",
+        "-f",
+        "ics",
+        "--exclude-breaks",
+        "-o",
+        path.to_str().unwrap(),
+    ]);
+    assert!(out.contains("Exported to"), "{out}");
+    let ics = std::fs::read_to_string(path).unwrap();
+    assert_eq!(1, ics.matches("BEGIN:VEVENT").count());
+}
+
+#[test]
+fn import_ics() {
+    let mut tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T12:00").ok(&["add", "coding"]);
+
+    let path = tracker.storage_dir().join("meetings.ics");
+    std::fs::write(
+        &path,
+        "BEGIN:VCALENDAR\r\n\
+        BEGIN:VEVENT\r\n\
+        DTSTART:20250724T100000\r\n\
+        DTEND:20250724T103000\r\n\
+        SUMMARY:Daily\r\n\
+        DESCRIPTION:Updates\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        DTSTART:20250725T100000\r\n\
+        DTEND:20250725T103000\r\n\
+        SUMMARY:Daily\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n",
+    )
+    .unwrap();
+    let path = path.to_str().unwrap();
+
+    let err = tracker.err(&["import", "--ics", path]);
+    assert!(err.contains("pass --yes"), "{err}");
+
+    let out = tracker.ok(&["import", "--ics", path, "-d", "2025-07-24", "--yes"]);
+    assert!(out.contains("Imported 2 entries"), "{out}");
+    let out = tracker.ok(&["view", "--long"]);
+    assert!(out.contains("10:00 : coding (1h)"), "{out}");
+    assert!(out.contains("10:30 : Daily (30m)"), "{out}");
+    assert!(out.contains("Updates"), "{out}");
+    assert!(out.contains("12:00 : coding (1h 30m)"), "{out}");
+
+    // Without a date, all events of the file are imported.
+    let out = tracker.ok(&["import", "--ics", path, "--yes"]);
+    assert!(out.contains("Imported 2 entries"), "{out}");
+    assert!(
+        tracker
+            .ok(&["view", "2025-07-25"])
+            .contains("10:30 : Daily")
+    );
+
+    let out = tracker.ok(&["import", "--ics", path, "--yes"]);
+    assert!(
+        out.contains(
+            "Imported 0 entries, as there is nothing new to import in 2025-07-24..2025-07-25"
+        ),
+        "{out}"
+    );
+}
+
+#[test]
+fn timew_round_trip() {
+    let mut tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T12:00").ok(&["add", "coding"]);
+    tracker.at("2025-07-24T12:30").ok(&["add", "break"]);
+    tracker.at("2025-07-24T17:00").ok(&["add", "review"]);
+
+    let json = tracker.ok(&["export", "-f", "timew"]);
+    assert_eq!(2, json.matches("\"start\"").count(), "{json}");
+    let path = tracker.storage_dir().join("timew.json");
+    std::fs::write(&path, json).unwrap();
+
+    // The import is not limited to today.
+    let mut other = Tracker::new();
+    other.at("2025-08-01T09:00");
+    let out = other.ok(&["import", "--timew", path.to_str().unwrap(), "--yes"]);
+    assert!(out.contains("Imported 4 entries"), "{out}");
+    let out = other.ok(&["view", "2025-07-24"]);
+    assert!(out.contains("12:00 : coding (3h)"), "{out}");
+    assert!(out.contains("12:30 : break (30m)"), "{out}");
+    assert!(out.contains("17:00 : review (4h 30m)"), "{out}");
+}
+
+#[test]
+fn export_xlsx_requires_output() {
+    let tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+
+    let err = tracker.err(&["export", "-f", "xlsx"]);
+    assert!(err.contains("requires --output"), "{err}");
+
+    let path = tracker.storage_dir().join("timesheet.xlsx");
+    tracker.ok(&["export", "-f", "xlsx", "-o", path.to_str().unwrap()]);
+    assert!(std::fs::read(path).unwrap().starts_with(b"PK"));
+}
+
+#[test]
+fn timesheet() {
+    let mut tracker = Tracker::with_config("employee = \"Jo Doe\"\nemployer = \"ACME\"");
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T17:00").ok(&["add", "end"]);
+
+    let html = tracker.storage_dir().join("july.html");
+    tracker.ok(&["timesheet", "-o", html.to_str().unwrap(), "--pdf"]);
+    let content = std::fs::read_to_string(&html).unwrap();
+    assert!(content.contains("<title>Timesheet July 2025</title>"));
+    assert!(content.contains("Jo Doe") && content.contains("ACME"));
+    assert!(content.contains("<td>2025-07-24</td><td>Thursday</td>"));
+    assert!(html.with_extension("pdf").is_file());
+
+    let err = tracker.err(&["timesheet", "this-year"]);
+    assert!(err.contains("at most 31 days"), "{err}");
+}
+
+#[test]
+fn invoice() {
+    let mut tracker = Tracker::with_config(
+        "[[clients]]\nname = \"Acme\"\ntag = \"acme\"\nrate = 60\nincrement = \"15m\"",
+    );
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T10:05").ok(&["add", "login #acme"]);
+    tracker.at("2025-07-24T11:00").ok(&["add", "mails"]);
+
+    let html = tracker.storage_dir().join("invoice.html");
+    let html = html.to_str().unwrap();
+    let draft = tracker.ok(&["invoice", "acme", "-d", "this-month", "-o", html, "--draft"]);
+    assert!(!draft.contains("Issued"), "{draft}");
+    assert!(
+        std::fs::read_to_string(html)
+            .unwrap()
+            .contains("Invoice (draft)")
+    );
+
+    let out = tracker.ok(&["invoice", "acme", "-d", "2025-07", "-o", html, "--json"]);
+    assert!(
+        out.contains("Issued invoice 2025-0001: 1:15 hours 75.00 EUR"),
+        "{out}"
+    );
+    let json = std::fs::read_to_string(std::path::Path::new(html).with_extension("json")).unwrap();
+    assert!(json.contains("\"number\": \"2025-0001\""), "{json}");
+
+    let err = tracker.err(&["invoice", "acme", "-d", "2025-07", "-o", html]);
+    assert!(err.contains("no uninvoiced entries of Acme"), "{err}");
+
+    let list = tracker.ok(&["invoice", "--list"]);
+    assert!(
+        list.contains("2025-0001 Acme 2025-07-01..2025-07-31 75.00 EUR"),
+        "{list}"
+    );
+}
+
+#[test]
+fn billable_report() {
+    let mut tracker =
+        Tracker::with_config("[[projects]]\nname = \"Web\"\ntag = \"web\"\nrate = 90");
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T11:00").ok(&["add", "layout #web"]);
+    tracker.at("2025-07-24T12:00").ok(&["add", "mails"]);
+    tracker
+        .at("2025-07-24T12:30")
+        .ok(&["add", "--billable", "call"]);
+
+    let view = tracker.ok(&["view"]);
+    assert!(view.contains("call #billable"), "{view}");
+    assert!(view.contains("2h 30m billable, 71%"), "{view}");
+
+    let report = tracker.ok(&["report", "--by", "month"]);
+    assert!(
+        report.contains("2025-07        3:30     0:00      2:30      71%     180.00 EUR"),
+        "{report}"
+    );
+}
+
+#[test]
+fn report_in_client_currency() {
+    let mut tracker = Tracker::with_config(
+        "[[clients]]\nname = \"Acme\"\ntag = \"acme\"\nrate = 100\ncurrency = \"USD\"\n\
+         [[clients]]\nname = \"Initech\"\ntag = \"initech\"\nrate = 80",
+    );
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T10:00").ok(&["add", "call #acme"]);
+
+    let report = tracker.ok(&["report", "--by", "month"]);
+    assert!(report.contains("100.00 USD"), "{report}");
+
+    tracker.at("2025-07-24T11:00").ok(&["add", "fix #initech"]);
+    let err = tracker.err(&["report"]);
+    assert!(
+        err.contains("can not sum up revenue in different currencies (EUR, USD)"),
+        "{err}"
+    );
+}
+
+#[test]
+fn rounded_billable_time() {
+    let mut tracker = Tracker::with_config(
+        "default_billable = true\nrounding = { step = \"15m\", per = \"day\", minimum = \"1h\" }",
+    );
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T09:20").ok(&["add", "call"]);
+    tracker.at("2025-07-25T09:00").ok(&["add", "start"]);
+    tracker.at("2025-07-25T10:07").ok(&["add", "fix"]);
+
+    let report = tracker.ok(&["report", "2025-07"]);
+    assert!(
+        report.contains("2025-W30       1:27     0:00      2:15"),
+        "{report}"
+    );
+    assert!(
+        tracker
+            .ok(&["view", "2025-07-25"])
+            .contains("10:07 : fix (1h 7m)")
+    );
+}
+
+#[test]
+fn altered_seal_time_is_detected() {
+    let mut tracker = Tracker::new();
+    tracker.ok(&["add", "start"]);
+    tracker.at("2025-07-24T12:00").ok(&["add", "end"]);
+    tracker.at("2025-08-01T09:00").ok(&["seal", "2025-07"]);
+    assert!(
+        tracker
+            .ok(&["verify"])
+            .contains("All 1 sealed periods are intact.")
+    );
+
+    let conn = rusqlite::Connection::open(tracker.storage_dir().join("db.sqlite")).unwrap();
+    conn.execute("UPDATE seal SET sealed_at = '2025-07-31T09:00:00'", [])
+        .unwrap();
+    drop(conn);
+
+    let output = tracker.run(&["verify"]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stdout)
+            .contains("seal has been altered or the chain is broken")
+    );
+}
@@ -0,0 +1,103 @@
+//! Harness to run the `tracker` binary against a temporary storage
+//! directory at a fixed point in time.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use tempfile::TempDir;
+
+pub struct Tracker {
+    dir: TempDir,
+    now: String,
+}
+
+impl Tracker {
+    /// Creates an empty storage directory and a config pointing to it. The
+    /// clock is fixed at `2025-07-24T09:00`.
+    pub fn new() -> Self {
+        Self::with_config("")
+    }
+
+    /// Like [`Tracker::new`], with additional config lines.
+    pub fn with_config(config: &str) -> Self {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let storage_dir = dir.path().join("store");
+        fs::write(
+            dir.path().join("config.toml"),
+            format!("storage_dir = {storage_dir:?}\n{config}"),
+        )
+        .expect("write config");
+        Self {
+            dir,
+            now: "2025-07-24T09:00".to_string(),
+        }
+    }
+
+    /// Sets the time the following commands run at.
+    pub fn at(&mut self, now: &str) -> &mut Self {
+        self.now = now.to_string();
+        self
+    }
+
+    pub fn storage_dir(&self) -> PathBuf {
+        self.dir.path().join("store")
+    }
+
+    /// Runs the binary with the given arguments and without a terminal.
+    pub fn run(&self, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_tracker"))
+            .arg("-c")
+            .arg(self.dir.path().join("config.toml"))
+            .args(args)
+            .env("TRACKER_NOW", &self.now)
+            // Keeps the legacy storage migration from looking at the real
+            // home directory.
+            .env("HOME", self.dir.path())
+            .env("XDG_CONFIG_HOME", self.dir.path().join("config"))
+            .env("XDG_DATA_HOME", self.dir.path().join("data"))
+            .stdin(Stdio::null())
+            .output()
+            .expect("run tracker")
+    }
+
+    /// Runs the binary, asserts that it succeeds and returns its output
+    /// without colors.
+    pub fn ok(&self, args: &[&str]) -> String {
+        let output = self.run(args);
+        let stdout = strip_ansi(&output.stdout);
+        assert!(
+            output.status.success(),
+            "tracker {args:?} failed:\n{stdout}{}",
+            strip_ansi(&output.stderr)
+        );
+        stdout
+    }
+
+    /// Runs the binary, asserts that it fails and returns its error output
+    /// without colors.
+    pub fn err(&self, args: &[&str]) -> String {
+        let output = self.run(args);
+        assert!(
+            !output.status.success(),
+            "tracker {args:?} succeeded:\n{}",
+            strip_ansi(&output.stdout)
+        );
+        strip_ansi(&output.stderr)
+    }
+}
+
+/// Removes ANSI escape sequences, as colors are emitted even when the
+/// output is not a terminal.
+pub fn strip_ansi(bytes: &[u8]) -> String {
+    let s = String::from_utf8_lossy(bytes);
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}